@@ -0,0 +1,267 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// What a player gets back for each move they make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bonus {
+    None,
+    // Fischer increment, added after every move
+    Increment(Duration),
+    // Bronstein delay, the time used is given back up to the delay
+    Bronstein(Duration),
+    // Simple delay, the clock only starts to run once the delay has passed
+    Delay(Duration),
+}
+
+/// One period of a time control, e.g. 40 moves in 90 minutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stage {
+    // The moves to make in this stage, or none when it lasts until the end of the game
+    pub moves: Option<u32>,
+    pub time: Duration,
+    pub bonus: Bonus,
+}
+
+/// The stages of a time control, written like `40/90+30, 30+30`:
+/// `[moves/]minutes` followed by `+seconds` of increment, `dseconds` of simple delay
+/// or `bseconds` of Bronstein delay, with the last stage repeating once reached
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimeControl {
+    pub stages: Vec<Stage>,
+}
+
+/// The time left on both clocks in milliseconds, as sent by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockState {
+    pub white: u64,
+    pub black: u64,
+    // The side whose clock is running, white(true) or black(false)
+    pub running: Option<bool>,
+}
+
+/// A chess clock for both players, with white(true) and black(false) as in the protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clock {
+    pub control: TimeControl,
+    left: [Duration; 2],
+    // The moves made by each side in its current stage
+    moves: [u32; 2],
+    stage: [usize; 2],
+    // The side whose clock is running, and since when
+    running: Option<(bool, Instant)>,
+}
+
+fn side(white: bool) -> usize {
+    if white {
+        0
+    } else {
+        1
+    }
+}
+
+fn parse_seconds(str: &str) -> Result<Duration, String> {
+    str.trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or(format!("Invalid number of seconds \"{}\"", str.trim()))
+}
+
+fn format_seconds(duration: Duration) -> String {
+    format!("{}", duration.as_secs_f64())
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let str = str.trim();
+        let (moves, rest) = match str.split_once('/') {
+            Some((moves, rest)) => {
+                let moves = moves
+                    .trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&moves| moves > 0)
+                    .ok_or(format!("Invalid number of moves \"{}\"", moves.trim()))?;
+                (Some(moves), rest)
+            }
+            None => (None, str),
+        };
+
+        let (minutes, bonus) = match rest.find(['+', 'd', 'b']) {
+            Some(i) => {
+                let seconds = parse_seconds(&rest[i + 1..])?;
+                let bonus = match &rest[i..i + 1] {
+                    "+" => Bonus::Increment(seconds),
+                    "d" => Bonus::Delay(seconds),
+                    _ => Bonus::Bronstein(seconds),
+                };
+                (&rest[..i], bonus)
+            }
+            None => (rest, Bonus::None),
+        };
+        let time = parse_seconds(minutes)
+            .map(|seconds| seconds * 60)
+            .map_err(|_| format!("Invalid number of minutes \"{}\"", minutes.trim()))?;
+        if time.is_zero() {
+            return Err("A stage needs some time on the clock".to_string());
+        }
+
+        Ok(Stage { moves, time, bonus })
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(moves) = self.moves {
+            write!(f, "{}/", moves)?;
+        }
+        write!(f, "{}", format_seconds(self.time / 60))?;
+        match self.bonus {
+            Bonus::None => Ok(()),
+            Bonus::Increment(seconds) => write!(f, "+{}", format_seconds(seconds)),
+            Bonus::Delay(seconds) => write!(f, "d{}", format_seconds(seconds)),
+            Bonus::Bronstein(seconds) => write!(f, "b{}", format_seconds(seconds)),
+        }
+    }
+}
+
+impl FromStr for TimeControl {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let stages = str
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<Stage>, _>>()?;
+        Ok(TimeControl { stages })
+    }
+}
+
+impl Display for TimeControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", stage)?;
+        }
+        Ok(())
+    }
+}
+
+impl TimeControl {
+    /// The stage a side is in, staying in the last one once reached
+    pub fn stage(&self, index: usize) -> &Stage {
+        &self.stages[index.min(self.stages.len() - 1)]
+    }
+}
+
+impl Clock {
+    pub fn new(control: TimeControl) -> Self {
+        let time = control.stage(0).time;
+        Clock {
+            control,
+            left: [time; 2],
+            moves: [0; 2],
+            stage: [0; 2],
+            running: None,
+        }
+    }
+
+    /// The side whose clock is running
+    pub fn running(&self) -> Option<bool> {
+        self.running.map(|(white, _)| white)
+    }
+
+    /// Starts the clock of a side, stopping the other one without a bonus
+    pub fn start(&mut self, white: bool, now: Instant) {
+        self.stop(now);
+        self.running = Some((white, now));
+    }
+
+    /// Stops the running clock, e.g. once the game is over
+    pub fn stop(&mut self, now: Instant) {
+        if let Some((white, _)) = self.running {
+            self.left[side(white)] = self.left(white, now);
+            self.running = None;
+        }
+    }
+
+    fn used(&self, white: bool, now: Instant) -> Duration {
+        match self.running {
+            Some((running, since)) if running == white => {
+                let elapsed = now.saturating_duration_since(since);
+                match self.control.stage(self.stage[side(white)]).bonus {
+                    Bonus::Delay(delay) => elapsed.saturating_sub(delay),
+                    _ => elapsed,
+                }
+            }
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// The time a side has left
+    pub fn left(&self, white: bool, now: Instant) -> Duration {
+        self.left[side(white)].saturating_sub(self.used(white, now))
+    }
+
+    /// Whether a side has run out of time
+    pub fn is_flagged(&self, white: bool, now: Instant) -> bool {
+        self.left(white, now).is_zero()
+    }
+
+    /// Ends the turn of the running side, adding its bonus and the time of its next stage,
+    /// and starts the opponent's clock, unless the flag had already fallen
+    pub fn press(&mut self, now: Instant) -> bool {
+        let Some((white, since)) = self.running else {
+            return true;
+        };
+        if self.is_flagged(white, now) {
+            self.stop(now);
+            return false;
+        }
+
+        let i = side(white);
+        let stage = *self.control.stage(self.stage[i]);
+        self.left[i] = self.left(white, now);
+        match stage.bonus {
+            Bonus::Increment(increment) => self.left[i] += increment,
+            Bonus::Bronstein(delay) => {
+                self.left[i] += now.saturating_duration_since(since).min(delay)
+            }
+            Bonus::None | Bonus::Delay(_) => {}
+        }
+
+        self.moves[i] += 1;
+        if stage.moves == Some(self.moves[i]) {
+            self.moves[i] = 0;
+            self.stage[i] += 1;
+            self.left[i] += self.control.stage(self.stage[i]).time;
+        }
+
+        self.running = Some((!white, now));
+        true
+    }
+
+    pub fn state(&self, now: Instant) -> ClockState {
+        ClockState {
+            white: self.left(true, now).as_millis() as u64,
+            black: self.left(false, now).as_millis() as u64,
+            running: self.running(),
+        }
+    }
+
+    /// Takes over the times of the authoritative clock, which counts from `now` on
+    pub fn set_state(&mut self, state: &ClockState, now: Instant) {
+        self.left = [
+            Duration::from_millis(state.white),
+            Duration::from_millis(state.black),
+        ];
+        self.running = state.running.map(|white| (white, now));
+    }
+}
@@ -1,17 +1,33 @@
+pub mod clock;
+
 use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
+use clock::ClockState;
+
 pub type RoomCode = u64;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub room: RoomCode,
+    // The board to start from if this creates the room, e.g. a Chess960 position
+    #[serde(default)]
+    pub board: Option<String>,
+    // The time control to play with if this creates the room, e.g. `5+3`
+    #[serde(default)]
+    pub time_control: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginResponse {
     pub player: bool,
+    // The board of the room, chosen by whoever created it
+    #[serde(default)]
+    pub board: Option<String>,
+    // The time control of the room, if the game is played on the clock
+    #[serde(default)]
+    pub time_control: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,6 +37,19 @@ pub struct CommandRequest {
     pub player: bool,
     pub cmd: String,
     pub board: String,
+    // How the game ended, if this command ended it
+    #[serde(default)]
+    pub termination: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayResponse {
+    // The clocks once the move has been made, the server keeps the time
+    #[serde(default)]
+    pub clock: Option<ClockState>,
+    // Whether the player had already run out of time, in which case the move was not made
+    #[serde(default)]
+    pub flagged: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +62,8 @@ pub struct QueryRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueryResponse {
     pub cmd: String,
+    #[serde(default)]
+    pub clock: Option<ClockState>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,6 +90,10 @@ pub struct LogBackRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LogBackResponse {
     pub board: String,
+    #[serde(default)]
+    pub time_control: Option<String>,
+    #[serde(default)]
+    pub clock: Option<ClockState>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,3 +1,4 @@
+use ::server::clock::*;
 use ::server::*;
 pub use async_attributes::{main, test};
 use async_std::sync::{Arc, RwLock};
@@ -6,21 +7,12 @@ use tide::log::*;
 use tide::{prelude::*, Error};
 use tide::{Request, StatusCode};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct RoomJoined {
     num: usize,
     assigned: bool,
 }
 
-impl Default for RoomJoined {
-    fn default() -> Self {
-        Self {
-            num: 0,
-            assigned: false,
-        }
-    }
-}
-
 impl RoomJoined {
     fn next(&mut self) -> Option<bool> {
         match self.num {
@@ -50,10 +42,17 @@ struct RoomInfo {
     joined: RoomJoined,
     queue: Option<String>,
     board: String,
+    // How the game ended, kept for the records
+    termination: Option<String>,
+    // The clocks of a timed game, only ever run here so that no client can tamper with them
+    clock: Option<Clock>,
+    // The player who ran out of time
+    flagged: Option<bool>,
     last_used: std::time::Instant,
 }
 
-static BOARD_DEFAULT: &str = "Rw0Pw0----Pb0Rb0Nw0Pw0----Pb0Nb0Bw0Pw0----Pb0Bb0Qw0Pw0----Pb0Qb0Kw0Pw0----Pb0Kb0Bw0Pw0----Pb0Bb0Nw0Pw0----Pb0Nb0Rw0Pw0----Pb0Rb0//1/P/f";
+// A new game in the client's versioned board format: variant, start position, moves, current position, status, termination and draw offer
+static BOARD_DEFAULT: &str = "v4;standard;rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1;;rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1;P;-;f";
 
 type RoomMap = HashMap<RoomCode, RoomInfo>;
 
@@ -114,22 +113,44 @@ async fn chess_login(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide::R
         if let std::collections::hash_map::Entry::Vacant(e) = map.entry(login.room) {
             let mut joined = RoomJoined::default();
             let player = joined.next().unwrap();
-            e.insert(RoomInfo {
+            // An invalid time control is played without a clock rather than refused
+            let clock = login
+                .time_control
+                .and_then(|control| control.parse::<TimeControl>().ok())
+                .map(Clock::new);
+            let info = e.insert(RoomInfo {
                 turn: true,
                 is_chat: false,
                 joined,
                 queue: None,
-                board: BOARD_DEFAULT.to_string(),
+                board: login.board.unwrap_or(BOARD_DEFAULT.to_string()),
+                termination: None,
+                clock,
+                flagged: None,
                 last_used: std::time::Instant::now(),
             });
             info!("New room created: {:?}", login.room);
-            Ok(json!(LoginResponse { player }).into())
+            Ok(json!(LoginResponse {
+                player,
+                board: Some(info.board.clone()),
+                time_control: info.clock.as_ref().map(|clock| clock.control.to_string())
+            })
+            .into())
         } else {
             let info = map.get_mut(&login.room).unwrap();
             match info.joined.next() {
                 Some(player) => {
                     info!("Player joined the room: {:?}", login.room);
-                    Ok(json!(LoginResponse { player }).into())
+                    // White's clock starts once both players are in
+                    if let Some(clock) = info.clock.as_mut() {
+                        clock.start(true, std::time::Instant::now());
+                    }
+                    Ok(json!(LoginResponse {
+                        player,
+                        board: Some(info.board.clone()),
+                        time_control: info.clock.as_ref().map(|clock| clock.control.to_string())
+                    })
+                    .into())
                 }
                 None => {
                     warn!("Room is full: {:?}", login.room);
@@ -148,19 +169,47 @@ async fn chess_play(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide::Re
         if map.contains_key(&command.room) {
             let info = map.get_mut(&command.room).unwrap();
             if info.joined.ok() {
-                info.last_used = std::time::Instant::now();
-
-                if info.turn == command.player && info.queue.is_none() {
+                let now = std::time::Instant::now();
+                info.last_used = now;
+
+                if info.flagged == Some(command.player) {
+                    info!("Player ran out of time in room: {:?}", command.room);
+                    Ok(json!(PlayResponse {
+                        clock: info.clock.as_ref().map(|clock| clock.state(now)),
+                        flagged: true
+                    })
+                    .into())
+                } else if info.turn == command.player && info.queue.is_none() {
                     if command.cmd.starts_with("chat") {
                         info.is_chat = true;
                         info!("Player sent a chat message in room: {:?}", command.room);
                     } else {
                         info!("Player played a move in room: {:?}", command.room);
+                        let in_time = info.clock.as_mut().is_none_or(|clock| clock.press(now));
+                        if !in_time {
+                            flag(info, command.player);
+                            return Ok(json!(PlayResponse {
+                                clock: info.clock.as_ref().map(|clock| clock.state(now)),
+                                flagged: true
+                            })
+                            .into());
+                        }
                     }
                     info.turn = !info.turn;
                     info.queue = Some(command.cmd);
                     info.board = command.board;
-                    Ok(json!({}).into())
+                    if let Some(termination) = command.termination {
+                        info!("Game ended by {} in room: {:?}", termination, command.room);
+                        info.termination = Some(termination);
+                        if let Some(clock) = info.clock.as_mut() {
+                            clock.stop(now);
+                        }
+                    }
+                    Ok(json!(PlayResponse {
+                        clock: info.clock.as_ref().map(|clock| clock.state(now)),
+                        flagged: false
+                    })
+                    .into())
                 } else {
                     info!(
                         "It's not your turn when playing in room: {:?}",
@@ -179,6 +228,18 @@ async fn chess_play(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide::Re
     }
 }
 
+/// Ends the game on time against `player`, telling the opponent with a `timeout` command
+fn flag(info: &mut RoomInfo, player: bool) {
+    info!("Player flagged: {:?}", player);
+    if let Some(clock) = info.clock.as_mut() {
+        clock.stop(std::time::Instant::now());
+    }
+    info.flagged = Some(player);
+    info.termination = Some("timeout".to_string());
+    info.turn = !player;
+    info.queue = Some("timeout".to_string());
+}
+
 async fn chess_query(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide::Result {
     let query: QueryRequest = req.body_json().await?;
     {
@@ -187,7 +248,19 @@ async fn chess_query(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide::R
         if map.contains_key(&query.room) {
             let info = map.get_mut(&query.room).unwrap();
             if info.joined.ok() {
-                info.last_used = std::time::Instant::now();
+                let now = std::time::Instant::now();
+                info.last_used = now;
+
+                // The opponent's flag falls while they think, without waiting for their move
+                let opponent_flagged = info.turn != query.player
+                    && info.queue.is_none()
+                    && info
+                        .clock
+                        .as_ref()
+                        .is_some_and(|clock| clock.is_flagged(info.turn, now));
+                if opponent_flagged {
+                    flag(info, info.turn);
+                }
 
                 if info.turn == query.player {
                     if let Some(cmd) = info.queue.clone() {
@@ -197,7 +270,11 @@ async fn chess_query(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide::R
                             info.is_chat = false;
                         }
                         info!("Player queried the room: {:?}", query.room);
-                        Ok(json!(QueryResponse { cmd }).into())
+                        Ok(json!(QueryResponse {
+                            cmd,
+                            clock: info.clock.as_ref().map(|clock| clock.state(now))
+                        })
+                        .into())
                     } else {
                         warn!("No move available in room: {:?}", query.room);
                         Err(Error::new(StatusCode::NotFound, ServerError))
@@ -261,8 +338,11 @@ async fn chess_log_back(mut req: Request<()>, map: Arc<RwLock<RoomMap>>) -> tide
             let info = e.get();
             if e.get().joined.ok() {
                 info!("Player logged back into room: {:?}", log_back.room);
+                let now = std::time::Instant::now();
                 Ok(json!(LogBackResponse {
-                    board: info.board.clone()
+                    board: info.board.clone(),
+                    time_control: info.clock.as_ref().map(|clock| clock.control.to_string()),
+                    clock: info.clock.as_ref().map(|clock| clock.state(now))
                 })
                 .into())
             } else {
@@ -0,0 +1,170 @@
+use super::*;
+
+/// The value of a piece in centipawns, the king has none as it is never traded
+pub fn piece_value(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Pawn => 100,
+        PieceKind::Knight => 320,
+        PieceKind::Bishop => 330,
+        PieceKind::Rook => 500,
+        PieceKind::Queen => 900,
+        PieceKind::King | PieceKind::Empty => 0,
+    }
+}
+
+// Piece-square tables from White's side, written as the board is seen with the 8th rank on top
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+// The king hides behind its pawns while there is material to attack it
+#[rustfmt::skip]
+const KING_MIDDLE_TABLE: [i32; 64] = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+// And walks to the center once the board has emptied
+#[rustfmt::skip]
+const KING_END_TABLE: [i32; 64] = [
+    -50,-40,-30,-20,-20,-30,-40,-50,
+    -30,-20,-10,  0,  0,-10,-20,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-30,  0,  0,  0,  0,-30,-30,
+    -50,-30,-30,-30,-30,-30,-30,-50,
+];
+
+// What the pieces of the starting position add up to in `phase_weight`
+const PHASE_TOTAL: i32 = 24;
+
+/// How much a piece counts towards the middlegame
+fn phase_weight(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Knight | PieceKind::Bishop => 1,
+        PieceKind::Rook => 2,
+        PieceKind::Queen => 4,
+        _ => 0,
+    }
+}
+
+fn table_index(square: Square, color: PieceColor) -> usize {
+    match color {
+        PieceColor::White => (7 - square.rank()) * 8 + square.file(),
+        PieceColor::Black => square.rank() * 8 + square.file(),
+    }
+}
+
+/// Scores the position in centipawns for the side to move, from material and piece placement
+pub fn evaluate(board: &Board) -> i32 {
+    let mut middle = 0;
+    let mut end = 0;
+    let mut phase = 0;
+
+    for color in [PieceColor::White, PieceColor::Black] {
+        let sign = match color {
+            PieceColor::White => 1,
+            PieceColor::Black => -1,
+        };
+        for square in board.pieces_of(color) {
+            let kind = board.get(square).kind;
+            let index = table_index(square, color);
+            let value = piece_value(kind);
+            phase += phase_weight(kind);
+
+            let (middle_bonus, end_bonus) = match kind {
+                PieceKind::Pawn => (PAWN_TABLE[index], PAWN_TABLE[index]),
+                PieceKind::Knight => (KNIGHT_TABLE[index], KNIGHT_TABLE[index]),
+                PieceKind::Bishop => (BISHOP_TABLE[index], BISHOP_TABLE[index]),
+                PieceKind::Rook => (ROOK_TABLE[index], ROOK_TABLE[index]),
+                PieceKind::Queen => (QUEEN_TABLE[index], QUEEN_TABLE[index]),
+                PieceKind::King => (KING_MIDDLE_TABLE[index], KING_END_TABLE[index]),
+                PieceKind::Empty => (0, 0),
+            };
+            middle += sign * (value + middle_bonus);
+            end += sign * (value + end_bonus);
+        }
+    }
+
+    // Blends the two scores by how much material is left, promotions may push the phase past the total
+    let phase = phase.min(PHASE_TOTAL);
+    let score = (middle * phase + end * (PHASE_TOTAL - phase)) / PHASE_TOTAL;
+    match board.which_color() {
+        PieceColor::White => score,
+        PieceColor::Black => -score,
+    }
+}
+
+/// Scores the position in centipawns for `color`, whoever is to move
+pub fn evaluate_for(board: &Board, color: PieceColor) -> i32 {
+    let score = evaluate(board);
+    if board.which_color() == color {
+        score
+    } else {
+        -score
+    }
+}
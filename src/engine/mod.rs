@@ -0,0 +1,9 @@
+mod eval;
+mod search;
+
+pub(super) use crate::game::*;
+pub(super) use crate::prelude::*;
+pub use eval::*;
+pub use search::*;
+pub(super) use std::sync::atomic::{AtomicBool, Ordering};
+pub(super) use std::time::Instant;
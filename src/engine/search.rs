@@ -0,0 +1,308 @@
+use super::*;
+
+/// The score of being checkmated right now, mates further away score a little less
+pub const MATE: i32 = 100_000;
+const INFINITY: i32 = 1_000_000;
+// Deep enough for any search that finishes in a reasonable time, quiescence included
+const MAX_PLY: usize = 64;
+
+/// How long the engine may think about a move, it stops at whichever limit comes first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    pub depth: Option<usize>,
+    pub movetime: Option<Duration>,
+    pub nodes: Option<u64>,
+}
+
+/// What the search has found once a depth is complete
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth: usize,
+    // In centipawns for the side to move, or `MATE` minus the distance to mate in plies
+    pub score: i32,
+    pub nodes: u64,
+    pub time: Duration,
+    // The expected line of play, starting with the best move
+    pub pv: Vec<Move>,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: usize) -> Self {
+        SearchLimits {
+            depth: Some(depth),
+            ..Default::default()
+        }
+    }
+
+    pub fn movetime(movetime: Duration) -> Self {
+        SearchLimits {
+            movetime: Some(movetime),
+            ..Default::default()
+        }
+    }
+
+    /// Shares out the time left on the clock, planning for `moves_to_go` more moves or a long game
+    pub fn clock(time: Duration, increment: Duration, moves_to_go: Option<u64>) -> Self {
+        let share = time / moves_to_go.unwrap_or(30).max(1) as u32 + increment * 3 / 4;
+        // Keeps a margin so that the flag never falls
+        let margin = time.saturating_sub(Duration::from_millis(50)) / 2;
+        SearchLimits::movetime(share.min(margin).max(Duration::from_millis(1)))
+    }
+}
+
+impl SearchInfo {
+    pub fn best_move(&self) -> Option<Move> {
+        self.pv.first().copied()
+    }
+
+    /// The number of moves until mate, negative when the side to move is getting mated
+    pub fn mate_in(&self) -> Option<i32> {
+        if self.score.abs() < MATE - MAX_PLY as i32 {
+            return None;
+        }
+        let plies = MATE - self.score.abs();
+        let moves = (plies + 1) / 2;
+        Some(if self.score > 0 { moves } else { -moves })
+    }
+}
+
+struct Search<'a> {
+    board: Board,
+    limits: SearchLimits,
+    stop: &'a AtomicBool,
+    start: Instant,
+    nodes: u64,
+    aborted: bool,
+    // The best line of each ply, built up from the leaves
+    pv: Vec<Vec<Move>>,
+    // The line found by the previous depth, tried first
+    previous_pv: Vec<Move>,
+    // Quiet moves that caused a cutoff at each ply, likely to do so again in sibling positions
+    killers: Vec<[Option<Move>; 2]>,
+}
+
+/// Searches the position by iterative deepening until a limit is hit or `stop` is set,
+/// calling `report` after each depth and returning the result of the last complete one
+pub fn search(
+    board: &Board,
+    limits: SearchLimits,
+    stop: &AtomicBool,
+    mut report: impl FnMut(&SearchInfo),
+) -> Option<SearchInfo> {
+    let mut search = Search {
+        board: board.clone(),
+        limits,
+        stop,
+        start: Instant::now(),
+        nodes: 0,
+        aborted: false,
+        pv: vec![Vec::new(); MAX_PLY + 1],
+        previous_pv: Vec::new(),
+        killers: vec![[None; 2]; MAX_PLY + 1],
+    };
+    if board.status != Status::Playing || board.legal_moves().is_empty() {
+        return None;
+    }
+
+    let mut result: Option<SearchInfo> = None;
+    let max_depth = limits.depth.unwrap_or(MAX_PLY).clamp(1, MAX_PLY);
+    for depth in 1..=max_depth {
+        let score = search.negamax(depth, 0, -INFINITY, INFINITY);
+        // A depth cut short is only trusted when there is nothing better
+        if search.aborted && result.is_some() {
+            break;
+        }
+
+        let info = SearchInfo {
+            depth,
+            score,
+            nodes: search.nodes,
+            time: search.start.elapsed(),
+            pv: search.pv[0].clone(),
+        };
+        report(&info);
+        search.previous_pv = info.pv.clone();
+        let is_mate = info.mate_in().is_some();
+        result = Some(info);
+
+        // The next depth takes several times as long, so it would not finish anyway
+        let is_late = limits
+            .movetime
+            .is_some_and(|movetime| search.start.elapsed() * 2 > movetime);
+        if search.aborted || is_mate || is_late {
+            break;
+        }
+    }
+    result
+}
+
+/// The best move within the limits, or nothing when the game is over
+pub fn best_move(board: &Board, limits: SearchLimits) -> Option<Move> {
+    search(board, limits, &AtomicBool::new(false), |_| {})?.best_move()
+}
+
+impl Search<'_> {
+    fn should_stop(&mut self) -> bool {
+        // The first depth always completes, so that there is a move to play
+        if self.aborted || self.previous_pv.is_empty() {
+            return self.aborted;
+        }
+        let is_out_of_time = self.nodes.is_multiple_of(1024)
+            && self
+                .limits
+                .movetime
+                .is_some_and(|movetime| self.start.elapsed() >= movetime);
+        let is_out_of_nodes = self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes);
+        if self.stop.load(Ordering::Relaxed) || is_out_of_time || is_out_of_nodes {
+            self.aborted = true;
+        }
+        self.aborted
+    }
+
+    /// Whether the position is drawn no matter what, repeating once inside the search is enough
+    fn is_draw(&self) -> bool {
+        self.board.repetitions() >= 2
+            || self.board.is_fifty_moves()
+            || self.board.is_insufficient_material()
+    }
+
+    fn negamax(&mut self, depth: usize, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv[ply].clear();
+        if self.should_stop() {
+            return 0;
+        }
+        self.nodes += 1;
+
+        if ply > 0 && self.is_draw() {
+            return 0;
+        }
+        if ply >= MAX_PLY {
+            return evaluate(&self.board);
+        }
+        // Checks are looked at one move further, so that mates and forced lines are not cut off
+        let depth = if self.board.is_check() {
+            depth + 1
+        } else {
+            depth
+        };
+        if depth == 0 {
+            return self.quiesce(ply, alpha, beta);
+        }
+
+        let mut moves = self.board.legal_moves();
+        if moves.is_empty() {
+            return if self.board.is_check() {
+                -MATE + ply as i32
+            } else {
+                0
+            };
+        }
+        self.order(&mut moves, ply);
+
+        for mv in moves {
+            let undo = self.board.make_move(mv);
+            let score = -self.negamax(depth - 1, ply + 1, -beta, -alpha);
+            self.board.unmake_move(undo);
+            if self.aborted {
+                return 0;
+            }
+
+            if score >= beta {
+                if self.captured(mv).is_none() && self.killers[ply][0] != Some(mv) {
+                    self.killers[ply] = [Some(mv), self.killers[ply][0]];
+                }
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+                let mut line = vec![mv];
+                line.extend_from_slice(&self.pv[ply + 1]);
+                self.pv[ply] = line;
+            }
+        }
+        alpha
+    }
+
+    /// Plays out the captures until the position is quiet, so that the evaluation is not fooled by a hanging piece
+    fn quiesce(&mut self, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv[ply].clear();
+        if self.should_stop() {
+            return 0;
+        }
+        self.nodes += 1;
+
+        if ply >= MAX_PLY {
+            return evaluate(&self.board);
+        }
+
+        // Out of check every move is looked at, as standing still is not an option
+        let in_check = self.board.is_check();
+        let mut moves = self.board.legal_moves();
+        if in_check {
+            if moves.is_empty() {
+                return -MATE + ply as i32;
+            }
+        } else {
+            let stand_pat = evaluate(&self.board);
+            if stand_pat >= beta {
+                return beta;
+            }
+            alpha = alpha.max(stand_pat);
+            moves.retain(|&mv| self.captured(mv).is_some() || mv.promotion.is_some());
+        }
+        self.order(&mut moves, ply);
+
+        for mv in moves {
+            let undo = self.board.make_move(mv);
+            let score = -self.quiesce(ply + 1, -beta, -alpha);
+            self.board.unmake_move(undo);
+            if self.aborted {
+                return 0;
+            }
+
+            if score >= beta {
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+                let mut line = vec![mv];
+                line.extend_from_slice(&self.pv[ply + 1]);
+                self.pv[ply] = line;
+            }
+        }
+        alpha
+    }
+
+    /// The kind of piece the move takes, en passant included
+    fn captured(&self, mv: Move) -> Option<PieceKind> {
+        let piece = self.board.get(mv.from);
+        let target = self.board.get(mv.to);
+        if target.is_enemy(piece.color) {
+            Some(target.kind)
+        } else if piece.is_kind(PieceKind::Pawn) && mv.from.file() != mv.to.file() {
+            Some(PieceKind::Pawn)
+        } else {
+            None
+        }
+    }
+
+    /// Sorts the moves most promising first: the previous best line, then captures of
+    /// valuable pieces by cheap ones, promotions and killer moves
+    fn order(&self, moves: &mut [Move], ply: usize) {
+        let previous = self.previous_pv.get(ply).copied();
+        moves.sort_by_cached_key(|&mv| {
+            let score = if Some(mv) == previous {
+                1_000_000
+            } else if let Some(victim) = self.captured(mv) {
+                100_000 + piece_value(victim) * 10 - piece_value(self.board.get(mv.from).kind)
+            } else if let Some(promotion) = mv.promotion {
+                90_000 + piece_value(promotion)
+            } else if self.killers[ply].contains(&Some(mv)) {
+                80_000
+            } else {
+                0
+            };
+            -score
+        });
+    }
+}
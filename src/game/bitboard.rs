@@ -0,0 +1,229 @@
+use super::*;
+
+/// A set of squares, where bit `rank * 8 + file` stands for the square
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bitboard(pub u64);
+
+pub struct BitboardIter(u64);
+
+// Directions for sliding pieces, the first four are positive(towards h8) and the others are negative
+const DIRECTIONS: [(isize, isize); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (-1, 1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (1, -1),
+];
+const ROOK_DIRECTIONS: [usize; 4] = [0, 2, 4, 6];
+const BISHOP_DIRECTIONS: [usize; 4] = [1, 3, 5, 7];
+
+lazy_static::lazy_static! {
+    static ref KNIGHT_ATTACKS: [Bitboard; 64] = leaper_table(&[
+        (-2, -1),
+        (-2, 1),
+        (-1, -2),
+        (-1, 2),
+        (1, -2),
+        (1, 2),
+        (2, -1),
+        (2, 1),
+    ]);
+    static ref KING_ATTACKS: [Bitboard; 64] = leaper_table(&[
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ]);
+    static ref PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+        leaper_table(&[(-1, 1), (1, 1)]),
+        leaper_table(&[(-1, -1), (1, -1)]),
+    ];
+    static ref RAYS: [[Bitboard; 64]; 8] = {
+        let mut rays = [[Bitboard::EMPTY; 64]; 8];
+        for (rays, &(file, rank)) in rays.iter_mut().zip(DIRECTIONS.iter()) {
+            for (index, ray) in rays.iter_mut().enumerate() {
+                let square = Square::from_index(index);
+                for distance in 1..=7 {
+                    match square.offset(file * distance, rank * distance) {
+                        Some(target) => ray.insert(target),
+                        None => break,
+                    }
+                }
+            }
+        }
+        rays
+    };
+}
+
+fn leaper_table(offsets: &[(isize, isize)]) -> [Bitboard; 64] {
+    let mut table = [Bitboard::EMPTY; 64];
+    for (index, bitboard) in table.iter_mut().enumerate() {
+        let square = Square::from_index(index);
+        for &(file, rank) in offsets {
+            if let Some(target) = square.offset(file, rank) {
+                bitboard.insert(target);
+            }
+        }
+    }
+    table
+}
+
+fn ray_attacks(square: Square, occupied: Bitboard, dir: usize) -> Bitboard {
+    let ray = RAYS[dir][square.index()];
+    let blockers = ray.0 & occupied.0;
+    if blockers == 0 {
+        return ray;
+    }
+
+    let blocker = if dir < 4 {
+        blockers.trailing_zeros()
+    } else {
+        63 - blockers.leading_zeros()
+    };
+    Bitboard(ray.0 ^ RAYS[dir][blocker as usize].0)
+}
+
+impl Bitboard {
+    pub const EMPTY: Self = Bitboard(0);
+    pub const LIGHT_SQUARES: Self = Bitboard(0x55aa_55aa_55aa_55aa);
+
+    pub fn from_square(square: Square) -> Self {
+        Bitboard(1 << square.index())
+    }
+
+    pub fn contains(&self, square: Square) -> bool {
+        self.0 & (1 << square.index()) != 0
+    }
+
+    pub fn insert(&mut self, square: Square) {
+        self.0 |= 1 << square.index();
+    }
+
+    pub fn remove(&mut self, square: Square) {
+        self.0 &= !(1 << square.index());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn first(&self) -> Option<Square> {
+        self.iter().next()
+    }
+
+    pub fn iter(&self) -> BitboardIter {
+        BitboardIter(self.0)
+    }
+
+    pub fn knight_attacks(square: Square) -> Self {
+        KNIGHT_ATTACKS[square.index()]
+    }
+
+    pub fn king_attacks(square: Square) -> Self {
+        KING_ATTACKS[square.index()]
+    }
+
+    /// The squares a pawn of `color` standing on `square` can capture on
+    pub fn pawn_attacks(square: Square, color: PieceColor) -> Self {
+        PAWN_ATTACKS[color as usize][square.index()]
+    }
+
+    pub fn rook_attacks(square: Square, occupied: Bitboard) -> Self {
+        ROOK_DIRECTIONS.iter().fold(Bitboard::EMPTY, |acc, &dir| {
+            acc | ray_attacks(square, occupied, dir)
+        })
+    }
+
+    pub fn bishop_attacks(square: Square, occupied: Bitboard) -> Self {
+        BISHOP_DIRECTIONS.iter().fold(Bitboard::EMPTY, |acc, &dir| {
+            acc | ray_attacks(square, occupied, dir)
+        })
+    }
+}
+
+impl Iterator for BitboardIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(Square::from_index(index))
+    }
+}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> BitboardIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(iter: I) -> Self {
+        let mut bitboard = Bitboard::EMPTY;
+        for square in iter {
+            bitboard.insert(square);
+        }
+        bitboard
+    }
+}
+
+impl std::ops::BitAnd for Bitboard {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitOr for Bitboard {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitXor for Bitboard {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::Not for Bitboard {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Bitboard(!self.0)
+    }
+}
+
+impl std::ops::BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl std::ops::BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -1,9 +1,11 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+// Written as its notation, e.g. `"e4"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct Square(usize, usize);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Status {
     Playing,
     White,
@@ -11,21 +13,76 @@ pub enum Status {
     Draw,
 }
 
-#[derive(Debug, Clone)]
+/// The rules the game is played by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Variant {
+    #[default]
+    Standard,
+    // Fischer Random, with the pieces on the back rank shuffled
+    Chess960,
+}
+
+/// How a finished game came to an end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Termination {
+    Checkmate,
+    Resignation,
+    Timeout,
+    Abandonment,
+    Stalemate,
+    Agreement,
+    // Claimed after three repetitions, or automatic after five
+    Repetition,
+    // Claimed after fifty moves, or automatic after seventy-five
+    FiftyMoves,
+    InsufficientMaterial,
+}
+
+/// Serialized through its `BoardRecord`, the caches are rebuilt by replaying the moves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "BoardRecord", try_from = "BoardRecord")]
 pub struct Board {
-    pub squares: [[Piece; 8]; 8],
+    // The pieces are kept both square by square and as bitboards, `set` keeps them in sync
+    pub(super) squares: [[Piece; 8]; 8],
+    pub(super) pieces: [Bitboard; 7],
+    pub(super) colors: [Bitboard; 2],
     pub turn: usize,
+    pub variant: Variant,
 
-    pub reachable: [[HashSet<Square>; 8]; 8],
-    pub threatened: HashSet<Square>,
     pub moves: Vec<Move>,
-
+    // The FEN of the position the game started from, unless it is the standard one
+    pub setup: Option<String>,
+    // The hashes of every position of the game so far, the current one included
+    pub history: Vec<u64>,
+    // Half moves since the last capture or pawn move, for the fifty-move rule
+    pub halfmove: usize,
+    pub castling: CastlingRights,
+    // The square skipped by a pawn that just moved two squares
+    pub en_passant: Option<Square>,
+
+    // Whether the side to move is in check or has no legal moves, worked out again after every move
     pub check: bool,
     pub no_safe: bool,
     pub status: Status,
+    // Why the game ended, while `status` tells who won
+    pub termination: Option<Termination>,
     pub draw_offer: bool,
+    // The clocks of a timed game, left out of saved boards
+    pub clock: Option<Clock>,
+}
+
+impl TryFrom<String> for Square {
+    type Error = String;
 
-    pub pool: Arc<RwLock<threadpool::ThreadPool>>,
+    fn try_from(notation: String) -> Result<Self, Self::Error> {
+        Square::from_notation(&notation).ok_or(format!("Invalid square {}", notation))
+    }
+}
+
+impl From<Square> for String {
+    fn from(square: Square) -> Self {
+        square.to_notation()
+    }
 }
 
 impl Square {
@@ -67,6 +124,14 @@ impl Square {
         }
     }
 
+    pub fn from_index(index: usize) -> Self {
+        Square(index % 8, index / 8)
+    }
+
+    pub fn index(&self) -> usize {
+        self.rank() * 8 + self.file()
+    }
+
     pub fn offset(&self, file: isize, rank: isize) -> Option<Self> {
         let file = self.file() as isize + file;
         let rank = self.rank() as isize + rank;
@@ -78,35 +143,32 @@ impl Square {
 }
 
 impl Board {
-    fn new_empty() -> Self {
-        let reachable: [[HashSet<Square>; 8]; 8] = Default::default();
-
+    pub(super) fn new_empty() -> Self {
         Board {
             squares: [[Piece::empty(); 8]; 8],
+            pieces: [Bitboard::EMPTY; 7],
+            colors: [Bitboard::EMPTY; 2],
             turn: 1,
+            variant: Variant::Standard,
 
-            reachable,
-            threatened: HashSet::new(),
             moves: Vec::new(),
+            setup: None,
+            history: Vec::new(),
+            halfmove: 0,
+            castling: CastlingRights::none(),
+            en_passant: None,
 
             check: false,
             no_safe: false,
             status: Status::Playing,
+            termination: None,
             draw_offer: false,
-
-            pool: Arc::new(RwLock::new(threadpool::ThreadPool::new(num_cpus::get()))),
+            clock: None,
         }
     }
 
     pub fn new() -> Self {
-        let mut board = Board::new_empty();
-
-        for file in 0..8 {
-            board.squares[file][1] = Piece::new(PieceKind::Pawn, PieceColor::White);
-            board.squares[file][6] = Piece::new(PieceKind::Pawn, PieceColor::Black);
-        }
-
-        let back_rank = [
+        Board::with_back_rank([
             PieceKind::Rook,
             PieceKind::Knight,
             PieceKind::Bishop,
@@ -115,14 +177,46 @@ impl Board {
             PieceKind::Bishop,
             PieceKind::Knight,
             PieceKind::Rook,
-        ];
+        ])
+    }
+
+    /// A new game with the pieces lined up on the back ranks as given, from the a-file to the h-file
+    pub(super) fn with_back_rank(back_rank: [PieceKind; 8]) -> Self {
+        let mut board = Board::new_empty();
+
+        for file in 0..8 {
+            board.set(
+                Square(file, 1),
+                Piece::new(PieceKind::Pawn, PieceColor::White),
+            );
+            board.set(
+                Square(file, 6),
+                Piece::new(PieceKind::Pawn, PieceColor::Black),
+            );
+        }
 
         for (file, &kind) in back_rank.iter().enumerate() {
-            board.squares[file][0] = Piece::new(kind, PieceColor::White);
-            board.squares[file][7] = Piece::new(kind, PieceColor::Black);
+            board.set(Square(file, 0), Piece::new(kind, PieceColor::White));
+            board.set(Square(file, 7), Piece::new(kind, PieceColor::Black));
         }
 
+        // Castling goes with the outermost rooks
+        let rooks = back_rank
+            .iter()
+            .enumerate()
+            .filter(|(_, &kind)| kind == PieceKind::Rook)
+            .map(|(file, _)| file)
+            .collect::<Vec<_>>();
+        for color in [PieceColor::White, PieceColor::Black] {
+            board
+                .castling
+                .set_rook(color, CastleSide::Queen, rooks.first().copied());
+            board
+                .castling
+                .set_rook(color, CastleSide::King, rooks.last().copied());
+        }
         board.update();
+        board.history.push(board.hash());
 
         board
     }
@@ -144,25 +238,37 @@ impl Board {
     }
 
     pub fn set(&mut self, square: Square, piece: Piece) {
+        let old = self.get(square);
+        if !old.is_empty() {
+            self.pieces[old.kind as usize].remove(square);
+            self.colors[old.color as usize].remove(square);
+        }
+        if !piece.is_empty() {
+            self.pieces[piece.kind as usize].insert(square);
+            self.colors[piece.color as usize].insert(square);
+        }
         self.squares[square.file()][square.rank()] = piece;
     }
 
+    /// All the pieces of the given kind and color
+    pub fn pieces(&self, kind: PieceKind, color: PieceColor) -> Bitboard {
+        self.pieces[kind as usize] & self.colors[color as usize]
+    }
+
+    pub fn pieces_of(&self, color: PieceColor) -> Bitboard {
+        self.colors[color as usize]
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+
+    pub fn king(&self, color: PieceColor) -> Option<Square> {
+        self.pieces(PieceKind::King, color).first()
+    }
+
     pub fn possible_en_passant(&self) -> Option<Square> {
-        let last_move = self.moves.last()?;
-
-        if last_move.to.rank() == 3
-            && last_move.from.rank() == 1
-            && self.get(last_move.to).kind == PieceKind::Pawn
-        {
-            Some(Square(last_move.to.file(), 2))
-        } else if last_move.to.rank() == 4
-            && last_move.from.rank() == 6
-            && self.get(last_move.to).kind == PieceKind::Pawn
-        {
-            Some(Square(last_move.to.file(), 5))
-        } else {
-            None
-        }
+        self.en_passant
     }
 
     pub fn force(&mut self, from: Square, to: Square) -> Option<()> {
@@ -226,29 +332,63 @@ impl Board {
         }
     }
 
-    pub fn resign(&mut self) {
-        self.status = match self.which_color() {
-            PieceColor::White => Status::Black,
-            PieceColor::Black => Status::White,
+    /// Ends the game with `color` winning, or drawn when there is no winner
+    pub fn finish(&mut self, winner: Option<PieceColor>, termination: Termination) {
+        self.status = match winner {
+            Some(PieceColor::White) => Status::White,
+            Some(PieceColor::Black) => Status::Black,
+            None => Status::Draw,
         };
+        self.termination = Some(termination);
+    }
+
+    pub fn resign(&mut self) {
+        self.finish(
+            Some(self.which_color().opposite()),
+            Termination::Resignation,
+        );
+    }
+
+    /// Ends the game because `color` has left it
+    pub fn abandon(&mut self, color: PieceColor) {
+        self.finish(Some(color.opposite()), Termination::Abandonment);
     }
 
     pub fn draw(&mut self) {
         if self.draw_offer {
-            self.status = Status::Draw;
+            self.finish(None, Termination::Agreement);
         } else {
             self.draw_offer = true;
             self.turn += 1;
         }
     }
 
+    /// Whether fifty moves have been played by each side without a capture or pawn move
+    pub fn is_fifty_moves(&self) -> bool {
+        self.halfmove >= 100
+    }
+
+    /// Claims a draw by threefold repetition or the fifty-move rule, returns whether the claim is valid
+    pub fn claim_draw(&mut self) -> bool {
+        if self.repetitions() >= 3 {
+            self.finish(None, Termination::Repetition);
+            true
+        } else if self.is_fifty_moves() {
+            self.finish(None, Termination::FiftyMoves);
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn decline_draw(&mut self) {
         self.draw_offer = false;
         self.turn -= 1;
     }
 
+    /// Whether the opponent of the side to move attacks the square
     pub fn is_threatened(&self, square: Square) -> bool {
-        self.threatened.contains(&square)
+        self.is_attacked(square, self.which_color().opposite())
     }
 
     pub fn is_check(&self) -> bool {
@@ -266,7 +406,7 @@ impl Board {
                 let piece = self.get(square);
                 print!("{} {}", square, piece);
                 print!(" ->");
-                for reachable in &self.reachable[file][rank] {
+                for reachable in self.reachable(square) {
                     print!(" {}", reachable);
                 }
                 println!()
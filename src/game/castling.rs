@@ -0,0 +1,184 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CastleSide {
+    King,
+    Queen,
+}
+
+/// The castlings each player is still allowed to do later in the game, whether or not they are possible right now
+// Each right is kept as the file of the rook it castles with, which only varies in Chess960
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct CastlingRights([[Option<usize>; 2]; 2]);
+
+impl CastleSide {
+    pub const ALL: [CastleSide; 2] = [CastleSide::King, CastleSide::Queen];
+
+    /// Where the rook starts in standard chess
+    pub fn rook_file(&self) -> usize {
+        match self {
+            CastleSide::King => 7,
+            CastleSide::Queen => 0,
+        }
+    }
+
+    /// Where the king lands after castling
+    pub fn king_target_file(&self) -> usize {
+        match self {
+            CastleSide::King => 6,
+            CastleSide::Queen => 2,
+        }
+    }
+
+    /// Where the rook lands after castling
+    pub fn rook_target_file(&self) -> usize {
+        match self {
+            CastleSide::King => 5,
+            CastleSide::Queen => 3,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CastleSide::King => "kingside",
+            CastleSide::Queen => "queenside",
+        }
+    }
+}
+
+impl CastlingRights {
+    pub fn all() -> Self {
+        let mut rights = CastlingRights::none();
+        for color in [PieceColor::White, PieceColor::Black] {
+            for side in CastleSide::ALL {
+                rights.set(color, side, true);
+            }
+        }
+        rights
+    }
+
+    pub fn none() -> Self {
+        CastlingRights::default()
+    }
+
+    pub fn has(&self, color: PieceColor, side: CastleSide) -> bool {
+        self.rook_file(color, side).is_some()
+    }
+
+    /// The file of the rook to castle with
+    pub fn rook_file(&self, color: PieceColor, side: CastleSide) -> Option<usize> {
+        self.0[color as usize][side as usize]
+    }
+
+    /// Grants or drops a right with the rook where it starts in standard chess
+    pub fn set(&mut self, color: PieceColor, side: CastleSide, allowed: bool) {
+        self.set_rook(color, side, allowed.then(|| side.rook_file()));
+    }
+
+    pub fn set_rook(&mut self, color: PieceColor, side: CastleSide, file: Option<usize>) {
+        self.0[color as usize][side as usize] = file;
+    }
+
+    pub fn remove_color(&mut self, color: PieceColor) {
+        self.0[color as usize] = [None; 2];
+    }
+
+    /// Drops the right bound to the rook on `square`, if any, because it has moved or been taken
+    pub fn remove_rook(&mut self, square: Square) {
+        for color in [PieceColor::White, PieceColor::Black] {
+            if square.rank() != color.back_rank() {
+                continue;
+            }
+            for side in CastleSide::ALL {
+                if self.rook_file(color, side) == Some(square.file()) {
+                    self.set_rook(color, side, None);
+                }
+            }
+        }
+    }
+
+    /// Whether every right castles with a rook from the corner, as in standard chess
+    pub fn is_standard(&self) -> bool {
+        [PieceColor::White, PieceColor::Black].iter().all(|&color| {
+            CastleSide::ALL.iter().all(|&side| {
+                self.rook_file(color, side)
+                    .is_none_or(|file| file == side.rook_file())
+            })
+        })
+    }
+}
+
+impl Board {
+    /// The side a king move castles to, if it is a castling
+    pub fn castle_side(&self, mv: Move) -> Option<CastleSide> {
+        let piece = self.get(mv.from);
+        let rank = piece.color.back_rank();
+        if !piece.is_kind(PieceKind::King) || mv.from.rank() != rank || mv.to.rank() != rank {
+            return None;
+        }
+
+        match self.variant {
+            // The king takes its own rook, since it may already stand on its target square
+            Variant::Chess960 => {
+                let target = self.get(mv.to);
+                if !(target.is_friend(piece.color) && target.is_kind(PieceKind::Rook)) {
+                    return None;
+                }
+                Some(if mv.to.file() > mv.from.file() {
+                    CastleSide::King
+                } else {
+                    CastleSide::Queen
+                })
+            }
+            Variant::Standard => match mv.to.file() as isize - mv.from.file() as isize {
+                2 => Some(CastleSide::King),
+                -2 => Some(CastleSide::Queen),
+                _ => None,
+            },
+        }
+    }
+
+    /// The move castling to `side` for the side to move, if it still has the right
+    pub fn castling_move(&self, side: CastleSide) -> Option<Move> {
+        let color = self.which_color();
+        let rank = color.back_rank();
+        let king = self.king(color)?;
+        let to = match self.variant {
+            Variant::Chess960 => Square::new(self.castling.rook_file(color, side)?, rank)?,
+            Variant::Standard => Square::new(side.king_target_file(), rank)?,
+        };
+        Some(Move::new_normal(king, to))
+    }
+
+    /// The squares that must be empty and the squares that must not be attacked to castle,
+    /// or nothing if the side to move has lost the right or its king and rook are not in place
+    pub(super) fn castling_squares(&self, side: CastleSide) -> Option<(Bitboard, Bitboard)> {
+        let color = self.which_color();
+        let rank = color.back_rank();
+        let file = self.castling.rook_file(color, side)?;
+        let king = self.king(color).filter(|king| king.rank() == rank)?;
+        let rook = self.get(Square::new(file, rank)?);
+        let is_outside = match side {
+            CastleSide::King => file > king.file(),
+            CastleSide::Queen => file < king.file(),
+        };
+        if !is_outside || !rook.is_friend(color) || !rook.is_kind(PieceKind::Rook) {
+            return None;
+        }
+
+        let span = |a: usize, b: usize| {
+            (a.min(b)..=a.max(b))
+                .map(|file| Square::new(file, rank).unwrap())
+                .collect::<Bitboard>()
+        };
+        let king_path = span(king.file(), side.king_target_file());
+        let rook_path = span(file, side.rook_target_file());
+
+        // The king and rook may pass over each other's squares
+        let mut empty = king_path | rook_path;
+        empty.remove(king);
+        empty.remove(Square::new(file, rank)?);
+
+        Some((empty, king_path))
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+/// How many starting positions Chess960 has
+pub const CHESS960_POSITIONS: usize = 960;
+
+/// The back rank of a Chess960 starting position, numbered as by Reinhard Scharnagl so that 518 is the standard one
+pub fn chess960_back_rank(id: usize) -> Option<[PieceKind; 8]> {
+    if id >= CHESS960_POSITIONS {
+        return None;
+    }
+
+    let mut rank = [PieceKind::Empty; 8];
+    let mut n = id;
+
+    // The bishops stand on squares of different colors
+    rank[(n % 4) * 2 + 1] = PieceKind::Bishop;
+    n /= 4;
+    rank[(n % 4) * 2] = PieceKind::Bishop;
+    n /= 4;
+
+    let mut place = |index: usize, kind: PieceKind| {
+        let file = (0..8)
+            .filter(|&file| rank[file] == PieceKind::Empty)
+            .nth(index)
+            .unwrap();
+        rank[file] = kind;
+    };
+    place(n % 6, PieceKind::Queen);
+    n /= 6;
+
+    let (first, second) = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+    ][n];
+    // Placing the second knight first keeps the index of the first one valid
+    place(second, PieceKind::Knight);
+    place(first, PieceKind::Knight);
+
+    // The king always ends up between the rooks
+    place(0, PieceKind::Rook);
+    place(0, PieceKind::King);
+    place(0, PieceKind::Rook);
+
+    Some(rank)
+}
+
+impl Variant {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Standard => "Standard",
+            Variant::Chess960 => "Chess960",
+        }
+    }
+}
+
+impl Board {
+    /// Plays the position by the rules of `variant`, which must allow its castling rights
+    pub fn set_variant(&mut self, variant: Variant) -> Result<(), Vec<PositionProblem>> {
+        let previous = self.variant;
+        self.variant = variant;
+        self.validate().inspect_err(|_| self.variant = previous)
+    }
+
+    /// A new Chess960 game from the starting position with the given number
+    pub fn new_chess960(id: usize) -> Option<Self> {
+        let mut board = Board::with_back_rank(chess960_back_rank(id)?);
+        board.variant = Variant::Chess960;
+        board.setup = Some(board.to_fen());
+        Some(board)
+    }
+
+    /// A new Chess960 game from a random starting position
+    pub fn new_chess960_random() -> Self {
+        Board::new_chess960(rand::random::<u64>() as usize % CHESS960_POSITIONS).unwrap()
+    }
+}
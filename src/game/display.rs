@@ -11,6 +11,16 @@ impl Display for Square {
     }
 }
 
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+        if let Some(promotion) = self.promotion.and_then(|kind| kind.to_notation()) {
+            write!(f, "{}", promotion.to_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for PieceColor {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(
@@ -36,7 +46,7 @@ impl Display for Piece {
             }
             str.push_str(unicode);
             for i in 0..CONFIG.spacing.div_euclid(2) {
-                if i == 0 && CONFIG.spacing % 2 == 0 && self.is_empty() {
+                if i == 0 && CONFIG.spacing.is_multiple_of(2) && self.is_empty() {
                     str.push_str(unicode);
                 } else {
                     str.push(' ');
@@ -87,18 +97,78 @@ impl Display for Status {
     }
 }
 
+impl Termination {
+    /// The headline of the end-of-game screen
+    pub fn describe(&self, board: &Board) -> &'static str {
+        match self {
+            Termination::Checkmate => "CHECKMATE!",
+            Termination::Resignation => "RESIGNATION!",
+            Termination::Timeout => "TIME OUT!",
+            Termination::Abandonment => "GAME ABANDONED!",
+            Termination::Stalemate => "STALEMATE!",
+            Termination::Agreement => "DRAW BY AGREEMENT!",
+            Termination::Repetition if board.repetitions() >= 5 => "FIVEFOLD REPETITION!",
+            Termination::Repetition => "THREEFOLD REPETITION!",
+            Termination::FiftyMoves if board.halfmove >= 150 => "SEVENTY-FIVE-MOVE RULE!",
+            Termination::FiftyMoves => "FIFTY-MOVE RULE!",
+            Termination::InsufficientMaterial => "INSUFFICIENT MATERIAL!",
+        }
+    }
+}
+
+/// Time on a clock as `h:mm:ss` or `m:ss`, with tenths once it is running out
+fn format_clock(left: Duration) -> String {
+    let seconds = left.as_secs();
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else if seconds >= 10 {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    } else {
+        format!("0:{:02}.{}", seconds, left.subsec_millis() / 100)
+    }
+}
+
 impl Board {
+    /// The clock of a side, next to its name
+    fn show_clock(&self, f: &mut Formatter, color: PieceColor) -> fmt::Result {
+        let Some(clock) = &self.clock else {
+            return write!(f, " |");
+        };
+        let white = color == PieceColor::White;
+        let left = clock.left(white, std::time::Instant::now());
+        let time = style(format!(" {} ", format_clock(left)));
+        let time = if left < Duration::from_secs(10) {
+            time.red()
+        } else {
+            time
+        };
+        if clock.running() == Some(white) {
+            write!(f, " | {}", time.bold().reverse())
+        } else {
+            write!(f, " | {}", time)
+        }
+    }
+
     fn show_rank_flip(&self, f: &mut Formatter, rank: usize, flip: bool) -> fmt::Result {
         if flip {
             match rank {
                 7 => write!(f, " > Black's Turn {}", self.turn / 2)?,
                 0 => write!(f, " - White")?,
+                6 => self.show_clock(f, PieceColor::Black)?,
+                1 => self.show_clock(f, PieceColor::White)?,
                 _ => write!(f, " |")?,
             }
         } else {
             match rank {
                 7 => write!(f, " - Black")?,
                 0 => write!(f, " > White's Turn {}", self.turn / 2 + 1)?,
+                6 => self.show_clock(f, PieceColor::Black)?,
+                1 => self.show_clock(f, PieceColor::White)?,
                 _ => write!(f, " |")?,
             }
         }
@@ -115,7 +185,7 @@ impl Board {
                     let is_used_tile = self
                         .moves
                         .last()
-                        .map_or(false, |m| m.from == square || m.to == square);
+                        .is_some_and(|m| m.from == square || m.to == square);
                     let is_dark_tile = (file + rank) % 2 == 1;
                     if is_used_tile {
                         write!(f, "{:+}", piece)?;
@@ -139,7 +209,11 @@ impl Board {
                     f,
                     "{}{}{}",
                     spacing,
-                    if CONFIG.spacing % 2 == 0 { c } else { ' ' },
+                    if CONFIG.spacing.is_multiple_of(2) {
+                        c
+                    } else {
+                        ' '
+                    },
                     c
                 )?;
             }
@@ -154,7 +228,7 @@ impl Board {
                     let is_used_tile = self
                         .moves
                         .last()
-                        .map_or(false, |m| m.from == square || m.to == square);
+                        .is_some_and(|m| m.from == square || m.to == square);
                     let is_dark_tile = (file + rank) % 2 == 1;
                     if is_used_tile {
                         write!(f, "{:+}", piece)?;
@@ -178,7 +252,11 @@ impl Board {
                     f,
                     "{}{}{}",
                     spacing,
-                    if CONFIG.spacing % 2 == 0 { c } else { ' ' },
+                    if CONFIG.spacing.is_multiple_of(2) {
+                        c
+                    } else {
+                        ' '
+                    },
                     c
                 )?;
             }
@@ -203,15 +281,31 @@ impl Display for Board {
                 if self.check {
                     writeln!(f, "{}", style("CHECK!").red())?;
                 }
+                if self.repetitions() >= 3 {
+                    writeln!(
+                        f,
+                        "{}",
+                        style("Threefold repetition! Type `claim` to claim a draw.").yellow()
+                    )?;
+                } else if self.is_fifty_moves() {
+                    writeln!(
+                        f,
+                        "{}",
+                        style("Fifty moves without a capture or pawn move! Type `claim` to claim a draw.")
+                            .yellow()
+                    )?;
+                }
             }
         } else {
             self.show_layout(f, flip)?;
 
-            if self.no_safe {
-                if self.check {
-                    writeln!(f, "{}", style("CHECKMATE!").red())?;
-                } else {
-                    writeln!(f, "{}", style("STALEMATE!").yellow())?;
+            if let Some(termination) = self.termination {
+                let message = style(termination.describe(self));
+                match termination {
+                    Termination::Checkmate | Termination::Timeout | Termination::Resignation => {
+                        writeln!(f, "{}", message.red())?
+                    }
+                    _ => writeln!(f, "{}", message.yellow())?,
                 }
             }
             writeln!(f, "Game has ended! Result: {}", self.status)?;
@@ -0,0 +1,203 @@
+use super::*;
+
+/// Declares an error that carries nothing but its message, such as a parse failure
+macro_rules! message_error {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident;) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        $vis struct $name {
+            pub msg: String,
+        }
+
+        impl $name {
+            pub fn new(msg: impl Into<String>) -> Self {
+                $name { msg: msg.into() }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.msg)
+            }
+        }
+
+        impl std::error::Error for $name {}
+    };
+}
+
+/// Builds a message error of the given type, e.g. `err!(FenError, "Invalid rank {}", rank)`
+macro_rules! err {
+    ($error:ident, $($msg:tt)*) => {
+        $error::new(format!($($msg)*))
+    };
+}
+
+pub(crate) use err;
+pub(crate) use message_error;
+
+/// Why the notation of a move could not be read
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NotationError {
+    Empty,
+    InvalidPiece(char),
+    // A square that is cut short or off the board
+    BadSquare(String),
+    MissingTarget,
+    // A file given alone, such as the `e` of `exd5`, that no single piece stands on
+    UnknownFile(char),
+    InvalidPromotion(String),
+    // No piece of the kind can move to the target square
+    NoMatch,
+    // Several pieces of the kind can move to the target square
+    Ambiguous,
+    NothingToTake,
+    TargetOccupied,
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NotationError::Empty => write!(f, "Piece code required"),
+            NotationError::InvalidPiece(piece) => write!(f, "Invalid piece code {}", piece),
+            NotationError::BadSquare(square) => write!(f, "Invalid square code {}", square),
+            NotationError::MissingTarget => write!(f, "Target square is not specified"),
+            NotationError::UnknownFile(file) => write!(
+                f,
+                "Shortcut square {} cannot be found, it either is ambiguous or does not exist",
+                file
+            ),
+            NotationError::InvalidPromotion(code) if code.is_empty() => {
+                write!(f, "Promotion code is missing")
+            }
+            NotationError::InvalidPromotion(code) => write!(f, "Invalid promotion code {}", code),
+            NotationError::NoMatch => write!(f, "No piece can move to the target square"),
+            NotationError::Ambiguous => {
+                write!(f, "Ambiguous move, please specify the source square")
+            }
+            NotationError::NothingToTake => {
+                write!(f, "No piece to take, remove 'x' from the notation")
+            }
+            NotationError::TargetOccupied => write!(
+                f,
+                "Target square is not empty, add 'x' in between to take the piece"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Why a move could not be read or played
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MoveError {
+    // The input does not describe a move on this board
+    Notation(NotationError),
+    GameOver,
+    NoPiece(Square),
+    WrongColor(Square),
+    // The piece does not move that way, or its path is blocked
+    Unreachable(Square, Square),
+    LeavesKingInCheck,
+    PinnedPiece(Square),
+    CastlingOutOfCheck,
+    CastlingThroughCheck,
+    CastlingNotAllowed,
+    MissingPromotion,
+    InvalidPromotion,
+}
+
+impl From<NotationError> for MoveError {
+    fn from(error: NotationError) -> Self {
+        MoveError::Notation(error)
+    }
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MoveError::Notation(error) => write!(f, "{}", error),
+            MoveError::GameOver => write!(f, "The game has already ended"),
+            MoveError::NoPiece(square) => write!(f, "There is no piece on {}", square),
+            MoveError::WrongColor(square) => {
+                write!(f, "The piece on {} belongs to your opponent", square)
+            }
+            MoveError::Unreachable(from, to) => {
+                write!(f, "The piece on {} cannot move to {}", from, to)
+            }
+            MoveError::LeavesKingInCheck => write!(f, "Invalid move! This leads to a check!"),
+            MoveError::PinnedPiece(square) => write!(
+                f,
+                "The piece on {} is pinned, moving it exposes your king",
+                square
+            ),
+            MoveError::CastlingOutOfCheck => write!(f, "You cannot castle out of check"),
+            MoveError::CastlingThroughCheck => {
+                write!(f, "You cannot castle through an attacked square")
+            }
+            MoveError::CastlingNotAllowed => write!(
+                f,
+                "You cannot castle, the king or rook has moved or the way is blocked"
+            ),
+            MoveError::MissingPromotion => write!(
+                f,
+                "Pawns must be promoted on the last rank, add '=...' to the notation"
+            ),
+            MoveError::InvalidPromotion => {
+                write!(f, "Only pawns reaching the last rank can be promoted")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Why a saved board could not be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    // The text or JSON is not laid out as a board
+    Malformed(String),
+    UnsupportedVersion(u32),
+    UnknownVariant(String),
+    InvalidStatus(String),
+    UnknownTermination(String),
+    InvalidDrawOffer(String),
+    // The position the moves start from
+    Fen(FenError),
+    InvalidMove(String),
+    IllegalMove(String),
+    // The moves do not lead to the saved position
+    PositionMismatch(String),
+    Position(Vec<PositionProblem>),
+}
+
+impl From<FenError> for RecordError {
+    fn from(error: FenError) -> Self {
+        RecordError::Fen(error)
+    }
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RecordError::Malformed(reason) => write!(f, "Invalid board, {}", reason),
+            RecordError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported board version {}", version)
+            }
+            RecordError::UnknownVariant(variant) => write!(f, "Unknown variant {}", variant),
+            RecordError::InvalidStatus(status) => write!(f, "Invalid status {}", status),
+            RecordError::UnknownTermination(termination) => {
+                write!(f, "Unknown termination {}", termination)
+            }
+            RecordError::InvalidDrawOffer(offer) => write!(f, "Invalid draw offer {}", offer),
+            RecordError::Fen(error) => write!(f, "Invalid starting position: {}", error),
+            RecordError::InvalidMove(mv) => write!(f, "Invalid move {}", mv),
+            RecordError::IllegalMove(mv) => write!(f, "Illegal move {}", mv),
+            RecordError::PositionMismatch(fen) => {
+                write!(f, "The moves do not lead to the position {}", fen)
+            }
+            RecordError::Position(problems) => write!(f, "{}", describe_problems(problems)),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
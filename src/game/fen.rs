@@ -0,0 +1,294 @@
+use super::*;
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+message_error! {
+    pub struct FenError;
+}
+
+impl Piece {
+    pub fn to_fen(&self) -> Option<char> {
+        let c = match self.kind {
+            PieceKind::Empty => return None,
+            PieceKind::Pawn => 'P',
+            kind => kind.to_notation()?.chars().next()?,
+        };
+        match self.color {
+            PieceColor::White => Some(c),
+            PieceColor::Black => Some(c.to_ascii_lowercase()),
+        }
+    }
+
+    pub fn from_fen(c: char) -> Option<Self> {
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+        let kind = PieceKind::from_notation(&c.to_ascii_uppercase().to_string())?;
+        let color = if c.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+        Some(Piece::new(kind, color))
+    }
+}
+
+impl Board {
+    /// Sets up a board from a FEN string, the two clock fields may be left out
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let mut board = Board::new_empty();
+        let fields = fen.split_whitespace().collect::<Vec<_>>();
+        if fields.len() < 4 || fields.len() > 6 {
+            return Err(err!(
+                FenError,
+                "Expected 6 fields separated by spaces, found {}",
+                fields.len()
+            ));
+        }
+
+        let ranks = fields[0].split('/').collect::<Vec<_>>();
+        if ranks.len() != 8 {
+            return Err(err!(
+                FenError,
+                "Expected 8 ranks separated by '/', found {}",
+                ranks.len()
+            ));
+        }
+        for (row, rank_str) in ranks.iter().enumerate() {
+            let rank = 7 - row;
+            let mut file = 0;
+            let mut was_digit = false;
+            for c in rank_str.chars() {
+                if let Some(skip @ 1..=8) = c.to_digit(10) {
+                    if was_digit {
+                        return Err(err!(FenError, "Consecutive digits on rank {}", rank + 1));
+                    }
+                    was_digit = true;
+                    file += skip as usize;
+                } else {
+                    was_digit = false;
+                    let piece = Piece::from_fen(c).ok_or(err!(
+                        FenError,
+                        "Invalid piece code '{}' on rank {}",
+                        c,
+                        rank + 1
+                    ))?;
+                    let square = Square::new(file, rank).ok_or(err!(
+                        FenError,
+                        "Rank {} has more than 8 squares",
+                        rank + 1
+                    ))?;
+                    board.set(square, piece);
+                    file += 1;
+                }
+            }
+            if file != 8 {
+                return Err(err!(
+                    FenError,
+                    "Rank {} has {} squares instead of 8",
+                    rank + 1,
+                    file
+                ));
+            }
+        }
+
+        let color = match fields[1] {
+            "w" => PieceColor::White,
+            "b" => PieceColor::Black,
+            side => {
+                return Err(err!(
+                    FenError,
+                    "Side to move must be 'w' or 'b', not '{}'",
+                    side
+                ))
+            }
+        };
+
+        board.castling = board.castling_from_fen(fields[2])?;
+        // Rooks away from the corners, or a king away from the e-file, can only castle in Chess960
+        let is_standard = board.castling.is_standard()
+            && [PieceColor::White, PieceColor::Black].iter().all(|&color| {
+                !CastleSide::ALL
+                    .iter()
+                    .any(|&side| board.castling.has(color, side))
+                    || board.king(color).map(|king| king.file()) == Some(4)
+            });
+        if !is_standard {
+            board.variant = Variant::Chess960;
+        }
+        board.en_passant = match fields[3] {
+            "-" => None,
+            square => Some(Square::from_notation(square).ok_or(err!(
+                FenError,
+                "Invalid en passant square '{}'",
+                square
+            ))?),
+        };
+
+        let halfmove = fields.get(4).copied().unwrap_or("0");
+        board.halfmove = halfmove
+            .parse()
+            .map_err(|_| err!(FenError, "Invalid halfmove clock '{}'", halfmove))?;
+        let fullmove = fields.get(5).copied().unwrap_or("1");
+        // Numbers too large to count the turns in are as invalid as zero
+        board.turn = fullmove
+            .parse::<usize>()
+            .ok()
+            .filter(|&fullmove| fullmove >= 1)
+            .and_then(|fullmove| fullmove.checked_mul(2))
+            .and_then(|turn| turn.checked_add((color == PieceColor::Black) as usize))
+            .map(|turn| turn - 1)
+            .ok_or(err!(FenError, "Invalid fullmove number '{}'", fullmove))?;
+
+        board
+            .validate()
+            .map_err(|problems| err!(FenError, "{}", describe_problems(&problems)))?;
+
+        board.update();
+        board.history.push(board.hash());
+        board.update_mate();
+
+        let fen = board.to_fen();
+        if fen != START_FEN {
+            board.setup = Some(fen);
+        }
+
+        Ok(board)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut placement = String::new();
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match self.get(Square::new(file, rank).unwrap()).to_fen() {
+                    Some(c) => {
+                        if empty > 0 {
+                            placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        placement.push(c);
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                placement.push('/');
+            }
+        }
+
+        format!(
+            "{} {} {} {} {} {}",
+            placement,
+            match self.which_color() {
+                PieceColor::White => 'w',
+                PieceColor::Black => 'b',
+            },
+            self.castling_to_fen(),
+            self.en_passant
+                .map_or("-".to_string(), |square| square.to_notation()),
+            self.halfmove,
+            self.turn.div_ceil(2)
+        )
+    }
+
+    /// Reads castling rights as in FEN (`KQkq`), Shredder-FEN (`HAha`) or X-FEN, which mixes both
+    pub(super) fn castling_from_fen(&self, s: &str) -> Result<CastlingRights, FenError> {
+        let mut rights = CastlingRights::none();
+        if s == "-" {
+            return Ok(rights);
+        }
+
+        for c in s.chars() {
+            let color = if c.is_ascii_uppercase() {
+                PieceColor::White
+            } else {
+                PieceColor::Black
+            };
+            let rank = color.back_rank();
+            let king = self
+                .king(color)
+                .filter(|king| king.rank() == rank)
+                .ok_or(err!(
+                    FenError,
+                    "{} cannot castle without its king on the back rank",
+                    color.name()
+                ))?;
+            let is_rook = |file: usize| {
+                let piece = self.get(Square::new(file, rank).unwrap());
+                piece.is_friend(color) && piece.is_kind(PieceKind::Rook)
+            };
+
+            // `K` and `Q` stand for the outermost rook on that side of the king
+            let (side, file) = match c.to_ascii_lowercase() {
+                'k' => (
+                    CastleSide::King,
+                    (king.file() + 1..8).rev().find(|&file| is_rook(file)),
+                ),
+                'q' => (
+                    CastleSide::Queen,
+                    (0..king.file()).find(|&file| is_rook(file)),
+                ),
+                letter @ 'a'..='h' => {
+                    let file = (letter as u8 - b'a') as usize;
+                    let side = if file > king.file() {
+                        CastleSide::King
+                    } else {
+                        CastleSide::Queen
+                    };
+                    (side, Some(file).filter(|&file| is_rook(file)))
+                }
+                _ => return Err(err!(FenError, "Invalid castling rights '{}'", s)),
+            };
+            let file = file.ok_or(err!(
+                FenError,
+                "{} cannot castle {} without its king and rook in place",
+                color.name(),
+                side.name()
+            ))?;
+            if rights.has(color, side) {
+                return Err(err!(FenError, "Repeated castling rights in '{}'", s));
+            }
+            rights.set_rook(color, side, Some(file));
+        }
+
+        Ok(rights)
+    }
+
+    /// Writes castling rights in X-FEN, which is plain FEN unless a rook other than the outermost one can castle
+    pub fn castling_to_fen(&self) -> String {
+        let mut s = String::new();
+        for color in [PieceColor::White, PieceColor::Black] {
+            let rank = color.back_rank();
+            for side in CastleSide::ALL {
+                let Some(file) = self.castling.rook_file(color, side) else {
+                    continue;
+                };
+                let outside = match side {
+                    CastleSide::King => file + 1..8,
+                    CastleSide::Queen => 0..file,
+                };
+                let is_outermost = outside.into_iter().all(|file| {
+                    let piece = self.get(Square::new(file, rank).unwrap());
+                    !(piece.is_friend(color) && piece.is_kind(PieceKind::Rook))
+                });
+                let c = match (is_outermost, side) {
+                    (true, CastleSide::King) => 'K',
+                    (true, CastleSide::Queen) => 'Q',
+                    (false, _) => (b'A' + file as u8) as char,
+                };
+                s.push(match color {
+                    PieceColor::White => c,
+                    PieceColor::Black => c.to_ascii_lowercase(),
+                });
+            }
+        }
+        if s.is_empty() {
+            s.push('-');
+        }
+        s
+    }
+}
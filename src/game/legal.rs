@@ -0,0 +1,157 @@
+use super::*;
+
+const PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+];
+
+impl Board {
+    /// Returns every fully legal move for the side to move, promotions included
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for file in 0..8 {
+            for rank in 0..8 {
+                moves.extend(self.legal_moves_from(Square::new(file, rank).unwrap()));
+            }
+        }
+        moves
+    }
+
+    /// Returns every fully legal move of the piece on `square`, or nothing if it is not the side to move
+    pub fn legal_moves_from(&self, square: Square) -> Vec<Move> {
+        self.pseudo_legal_moves_from(square)
+            .into_iter()
+            .filter(|mv| self.is_safe_after(*mv))
+            .collect()
+    }
+
+    pub fn is_legal(&self, mv: Move) -> bool {
+        self.pseudo_legal_moves_from(mv.from).contains(&mv) && self.is_safe_after(mv)
+    }
+
+    /// Like `is_legal`, but tells what is wrong with an illegal move
+    pub fn check_move(&self, mv: Move) -> Result<(), MoveError> {
+        if self.status != Status::Playing {
+            return Err(MoveError::GameOver);
+        }
+        let piece = self.get(mv.from);
+        let color = self.which_color();
+        if piece.is_empty() {
+            return Err(MoveError::NoPiece(mv.from));
+        }
+        if !piece.is_color(color) {
+            return Err(MoveError::WrongColor(mv.from));
+        }
+        if self.is_legal(mv) {
+            return Ok(());
+        }
+
+        let is_last_rank = mv.to.rank() == 0 || mv.to.rank() == 7;
+        if mv.promotion.is_some() && !(piece.is_kind(PieceKind::Pawn) && is_last_rank) {
+            return Err(MoveError::InvalidPromotion);
+        }
+
+        if let Some(side) = self.castle_side(mv) {
+            let enemy = color.opposite();
+            let target = Square::new(side.king_target_file(), color.back_rank()).unwrap();
+            return Err(match self.castling_squares(side) {
+                Some((empty, safe)) if (empty & self.occupied()).is_empty() => {
+                    if self.check {
+                        MoveError::CastlingOutOfCheck
+                    } else if safe
+                        .iter()
+                        .any(|square| square != target && self.is_attacked(square, enemy))
+                    {
+                        MoveError::CastlingThroughCheck
+                    } else {
+                        MoveError::LeavesKingInCheck
+                    }
+                }
+                _ => MoveError::CastlingNotAllowed,
+            });
+        }
+
+        if !self.reachable(mv.from).contains(mv.to) {
+            return Err(MoveError::Unreachable(mv.from, mv.to));
+        }
+        if piece.is_kind(PieceKind::Pawn) && is_last_rank && mv.promotion.is_none() {
+            return Err(MoveError::MissingPromotion);
+        }
+
+        // Without a check to answer, only a pin can make a move of another piece than the king unsafe
+        if !self.check && !piece.is_kind(PieceKind::King) {
+            Err(MoveError::PinnedPiece(mv.from))
+        } else {
+            Err(MoveError::LeavesKingInCheck)
+        }
+    }
+
+    /// Moves that follow the piece movement rules, but may leave the king in check
+    pub(super) fn pseudo_legal_moves_from(&self, square: Square) -> Vec<Move> {
+        let piece = self.get(square);
+        if !piece.is_friend(self.which_color()) {
+            return Vec::new();
+        }
+
+        let mut moves = Vec::new();
+        for target in self.reachable(square) {
+            if piece.is_kind(PieceKind::Pawn) && (target.rank() == 0 || target.rank() == 7) {
+                for promotion in PROMOTIONS {
+                    moves.push(Move::new(square, target, Some(promotion)));
+                }
+            } else {
+                moves.push(Move::new_normal(square, target));
+            }
+        }
+        moves
+    }
+
+    /// Whether the king of the side to move is safe after the move, checked on the bitboards without playing it
+    fn is_safe_after(&self, mv: Move) -> bool {
+        let piece = self.get(mv.from);
+        let color = piece.color;
+
+        let mut occupied = self.occupied();
+        occupied.remove(mv.from);
+        occupied.insert(mv.to);
+        let mut captured = Bitboard::from_square(mv.to);
+        if piece.is_kind(PieceKind::Pawn)
+            && mv.from.file() != mv.to.file()
+            && self.get(mv.to).is_empty()
+        {
+            let passed = Square::new(mv.to.file(), mv.from.rank()).unwrap();
+            occupied.remove(passed);
+            captured.insert(passed);
+        }
+
+        let king = if let Some(side) = self.castle_side(mv) {
+            // The king and rook both leave their squares, in Chess960 possibly for each other's
+            let rank = mv.from.rank();
+            let rook = Square::new(
+                self.castling
+                    .rook_file(color, side)
+                    .unwrap_or(side.rook_file()),
+                rank,
+            )
+            .unwrap();
+            let king = Square::new(side.king_target_file(), rank).unwrap();
+            occupied = self.occupied();
+            occupied.remove(mv.from);
+            occupied.remove(rook);
+            occupied.insert(king);
+            occupied.insert(Square::new(side.rook_target_file(), rank).unwrap());
+            captured = Bitboard::EMPTY;
+            king
+        } else if piece.is_kind(PieceKind::King) {
+            mv.to
+        } else {
+            match self.king(color) {
+                Some(king) => king,
+                None => return true,
+            }
+        };
+        (self.attackers(king, color.opposite(), occupied) & !captured).is_empty()
+    }
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+impl Board {
+    /// Whether neither side can possibly checkmate, whatever is played
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy = [PieceKind::Pawn, PieceKind::Rook, PieceKind::Queen];
+        if heavy
+            .iter()
+            .any(|&kind| !self.pieces[kind as usize].is_empty())
+        {
+            return false;
+        }
+
+        let knights = self.pieces[PieceKind::Knight as usize];
+        let bishops = self.pieces[PieceKind::Bishop as usize];
+        if knights.count() + bishops.count() <= 1 {
+            return true;
+        }
+
+        // Any number of bishops, all running on the same color, can never mate
+        knights.is_empty()
+            && ((bishops & Bitboard::LIGHT_SQUARES).is_empty()
+                || (bishops & !Bitboard::LIGHT_SQUARES).is_empty())
+    }
+}
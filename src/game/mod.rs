@@ -1,15 +1,40 @@
-mod serialize;
+mod bitboard;
 mod board;
+mod castling;
+mod chess960;
 mod display;
+mod error;
+mod fen;
+mod legal;
+mod material;
 mod moves;
 mod notation;
+mod perft;
+mod pgn;
+mod pgn_import;
 mod piece;
 mod reach;
+mod san;
+mod serialize;
+mod timing;
 mod update;
+mod validate;
+mod zobrist;
 
+pub use bitboard::*;
 pub use board::*;
+pub use castling::*;
+pub use chess960::*;
+pub use error::*;
+pub use fen::*;
 pub use moves::*;
-pub use notation::*;
+pub use pgn::*;
+pub use pgn_import::*;
 pub use piece::*;
+pub use serialize::*;
+pub use validate::*;
+
+// Shared with the server, which keeps the clocks of online games
+pub use ::server::clock::*;
 
 pub use crate::prelude::*;
@@ -1,12 +1,32 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Move {
     pub from: Square,
     pub to: Square,
     pub promotion: Option<PieceKind>,
 }
 
+/// Everything `unmake_move` needs to take a move back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Undo {
+    pub mv: Move,
+    // The moved piece as it was before the move
+    pub piece: Piece,
+    pub captured: Piece,
+    // Differs from the target square only when capturing en passant
+    pub captured_square: Square,
+    // The rook moved by castling, from and to where
+    pub rook: Option<(Square, Square, Piece)>,
+    pub halfmove: usize,
+    pub castling: CastlingRights,
+    pub en_passant: Option<Square>,
+    pub check: bool,
+    pub no_safe: bool,
+    pub status: Status,
+    pub termination: Option<Termination>,
+}
+
 impl Move {
     pub fn new(from: Square, to: Square, promotion: Option<PieceKind>) -> Self {
         Move {
@@ -31,57 +51,144 @@ impl Move {
 }
 
 impl Board {
-    pub fn perform(&self, mv: Move) -> Option<Self> {
+    /// Plays a move on a copy of the board, or tells why it is not allowed
+    pub fn perform(&self, mv: Move) -> Result<Self, MoveError> {
+        self.check_move(mv)?;
+
         let mut board = self.clone();
+        board.make_move(mv);
+        board.update_mate();
 
-        let piece = board.get(mv.from);
-        let target = board.get(mv.to);
+        Ok(board)
+    }
+
+    /// Plays the move in place and passes the turn, the move must be legal.
+    ///
+    /// Only `check` is refreshed, call `update_mate` to detect the end of the game
+    pub fn make_move(&mut self, mv: Move) -> Undo {
+        let piece = self.get(mv.from);
+        let mut undo = Undo {
+            mv,
+            piece,
+            captured: self.get(mv.to),
+            captured_square: mv.to,
+            rook: None,
+            halfmove: self.halfmove,
+            castling: self.castling,
+            en_passant: self.en_passant,
+            check: self.check,
+            no_safe: self.no_safe,
+            status: self.status,
+            termination: self.termination,
+        };
 
         if piece.is_kind(PieceKind::King) {
-            let distance = mv.to.file() as isize - mv.from.file() as isize;
-            if distance == -2 {
-                board.force(mv.from, mv.to);
-                board.force(
-                    Square::new(0, mv.from.rank()).unwrap(),
-                    Square::new(3, mv.from.rank()).unwrap(),
+            if let Some(side) = self.castle_side(mv) {
+                let rank = mv.from.rank();
+                let file = self
+                    .castling
+                    .rook_file(piece.color, side)
+                    .unwrap_or(side.rook_file());
+                let from = Square::new(file, rank).unwrap();
+                let to = Square::new(side.rook_target_file(), rank).unwrap();
+                let rook = self.get(from);
+                undo.rook = Some((from, to, rook));
+                // In Chess960 the move's target is the king's own rook, which is not captured
+                undo.captured = Piece::empty();
+
+                // Both leave first, as they may land on each other's squares
+                self.set(mv.from, Piece::empty());
+                self.set(from, Piece::empty());
+                let king = Square::new(side.king_target_file(), rank).unwrap();
+                self.set(
+                    king,
+                    Piece {
+                        previous: self.turn,
+                        ..piece
+                    },
                 );
-            } else if distance == 2 {
-                board.force(mv.from, mv.to);
-                board.force(
-                    Square::new(7, mv.from.rank()).unwrap(),
-                    Square::new(5, mv.from.rank()).unwrap(),
+                self.set(
+                    to,
+                    Piece {
+                        previous: self.turn,
+                        ..rook
+                    },
                 );
             } else {
-                board.force(mv.from, mv.to);
+                self.force(mv.from, mv.to);
             }
+            self.castling.remove_color(piece.color);
         } else if piece.is_kind(PieceKind::Pawn) {
             if mv.to.rank() == 0 || mv.to.rank() == 7 {
-                board.set(mv.to, Piece::new(mv.promotion.unwrap(), piece.color));
-                board.set(mv.from, Piece::empty());
-            } else if mv.to.file() != mv.from.file() && target.is_empty() {
-                board.set(
-                    Square::new(mv.to.file(), mv.from.rank()).unwrap(),
-                    Piece::empty(),
-                );
-                board.force(mv.from, mv.to);
+                self.set(mv.to, Piece::new(mv.promotion.unwrap(), piece.color));
+                self.set(mv.from, Piece::empty());
+            } else if mv.to.file() != mv.from.file() && undo.captured.is_empty() {
+                undo.captured_square = Square::new(mv.to.file(), mv.from.rank()).unwrap();
+                undo.captured = self.get(undo.captured_square);
+                self.set(undo.captured_square, Piece::empty());
+                self.force(mv.from, mv.to);
             } else {
-                board.force(mv.from, mv.to);
+                self.force(mv.from, mv.to);
             }
         } else {
-            board.force(mv.from, mv.to);
+            self.force(mv.from, mv.to);
         }
 
-        board.update();
-        if board.is_check() {
-            None
+        self.castling.remove_rook(mv.from);
+        self.castling.remove_rook(mv.to);
+
+        self.en_passant =
+            if piece.is_kind(PieceKind::Pawn) && mv.from.rank().abs_diff(mv.to.rank()) == 2 {
+                Square::new(mv.from.file(), (mv.from.rank() + mv.to.rank()) / 2)
+            } else {
+                None
+            };
+
+        if piece.is_kind(PieceKind::Pawn) || !undo.captured.is_empty() {
+            self.halfmove = 0;
         } else {
-            board.moves.push(mv);
+            self.halfmove += 1;
+        }
+
+        self.moves.push(mv);
+        self.turn += 1;
+        self.no_safe = false;
+        self.update();
+        self.history.push(self.hash());
+
+        undo
+    }
+
+    /// Takes back the move that produced `undo`, which must be the last one made
+    pub fn unmake_move(&mut self, undo: Undo) {
+        let mv = undo.mv;
 
-            board.turn += 1;
-            board.update();
-            board.update_mate();
+        self.turn -= 1;
+        self.moves.pop();
+        self.history.pop();
 
-            Some(board)
+        if let Some((from, to, rook)) = undo.rook {
+            let side = if to.file() == CastleSide::King.rook_target_file() {
+                CastleSide::King
+            } else {
+                CastleSide::Queen
+            };
+            let king = Square::new(side.king_target_file(), mv.from.rank()).unwrap();
+            self.set(king, Piece::empty());
+            self.set(to, Piece::empty());
+            self.set(from, rook);
+        } else {
+            self.set(mv.to, Piece::empty());
+            self.set(undo.captured_square, undo.captured);
         }
+        self.set(mv.from, undo.piece);
+
+        self.halfmove = undo.halfmove;
+        self.castling = undo.castling;
+        self.en_passant = undo.en_passant;
+        self.check = undo.check;
+        self.no_safe = undo.no_safe;
+        self.status = undo.status;
+        self.termination = undo.termination;
     }
 }
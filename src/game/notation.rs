@@ -1,11 +1,5 @@
 use super::*;
 
-macro_rules! err {
-    ($($msg:tt)*) => {
-        NotationError::new(&format!($($msg)*))
-    };
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Notation {
     piece: PieceKind,
@@ -15,75 +9,76 @@ struct Notation {
     promotion: Option<PieceKind>,
 }
 
-#[derive(Debug, Clone)]
-pub struct NotationError {
-    pub msg: String,
-}
+impl Board {
+    /// Reads a move in SAN, in UCI long algebraic notation or in one of the shorthand forms
+    pub fn translate(&self, notation: &str) -> Result<Move, MoveError> {
+        let notation = normalize(notation);
 
-impl NotationError {
-    pub fn new(msg: &str) -> Self {
-        NotationError {
-            msg: msg.to_string(),
+        // Strict SAN, as found in PGN files, is matched against the legal moves first
+        if let Some(mv) = self
+            .legal_moves()
+            .into_iter()
+            .find(|&mv| self.san_body(mv) == notation)
+        {
+            return Ok(mv);
         }
-    }
-}
 
-impl std::fmt::Display for NotationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.msg)
-    }
-}
-
-impl Board {
-    pub fn translate(&self, notation: &str) -> Result<Move, NotationError> {
-        let notation = self.split_notation(notation)?;
+        let notation = self.split_notation(&notation)?;
 
         let color = self.which_color();
 
-        let from = if notation.from.is_none() {
-            let mut candidate = None;
-            for file in 0..8 {
-                for rank in 0..8 {
-                    let square = Square::new(file, rank).unwrap();
-                    let piece = self.get(square);
-                    if !piece.is_empty()
-                        && piece.is_color(color)
-                        && piece.is_kind(notation.piece)
-                        && self.reachable[square.file()][square.rank()].contains(&notation.to)
-                    {
-                        if candidate.is_none() {
-                            candidate = Some(square);
+        // Whether an explicitly specified piece can really get there is left to `perform`, which tells what is wrong
+        let from = if let Some(from) = notation.from {
+            from
+        } else {
+            // Only legal moves are considered for disambiguation, and pseudo-legal ones are kept as a fallback so that `perform` can reject them
+            let find = |legal: bool| {
+                let mut candidates = Vec::new();
+                for file in 0..8 {
+                    for rank in 0..8 {
+                        let square = Square::new(file, rank).unwrap();
+                        let piece = self.get(square);
+                        if !piece.is_color(color) || !piece.is_kind(notation.piece) {
+                            continue;
+                        }
+                        let moves = if legal {
+                            self.legal_moves_from(square)
                         } else {
-                            return Err(err!("Ambiguous move, please specify the source square"));
+                            self.pseudo_legal_moves_from(square)
+                        };
+                        if moves.iter().any(|mv| mv.to == notation.to) {
+                            candidates.push(square);
                         }
                     }
                 }
+                candidates
+            };
+
+            let mut candidates = find(true);
+            if candidates.is_empty() {
+                candidates = find(false);
             }
-            candidate.ok_or(err!("No piece can move to the target square"))?
-        } else {
-            let from = notation.from.unwrap();
-            if self.reachable[from.file()][from.rank()].contains(&notation.to) {
-                from
-            } else {
-                return Err(err!(
-                    "The explicitly specified piece {} cannot move to the target square",
-                    from
-                ));
+            match candidates.len() {
+                0 => return Err(NotationError::NoMatch.into()),
+                1 => candidates[0],
+                _ => return Err(NotationError::Ambiguous.into()),
             }
         };
         let to = notation.to;
 
+        let is_en_passant = self.get(from).is_kind(PieceKind::Pawn)
+            && self.possible_en_passant() == Some(to)
+            && from.file() != to.file();
         if notation.take {
             let piece = self.get(to);
-            if piece.is_empty() || piece.is_color(color) {
-                return Err(err!("No piece to take, remove 'x' from the notation"));
+            if !piece.is_enemy(color) && !is_en_passant {
+                return Err(NotationError::NothingToTake.into());
             }
-        } else {
+        } else if notation.from.is_none() {
+            // Long algebraic notation such as `e4d5` may leave out the 'x'
             let piece = self.get(to);
             if !piece.is_empty() {
-                return Err(err!(
-                    "Target square is not empty, add 'x' in between to take the piece"
-                ));
+                return Err(NotationError::TargetOccupied.into());
             }
         }
 
@@ -91,49 +86,46 @@ impl Board {
 
         if let Some(promotion) = notation.promotion {
             if !piece.is_kind(PieceKind::Pawn) {
-                return Err(err!("Only pawns can be promoted"));
+                return Err(MoveError::InvalidPromotion);
             }
 
             if let 0 | 7 = to.rank() {
+                let code = match promotion {
+                    PieceKind::Pawn => "P",
+                    _ => promotion.to_notation().unwrap_or_default(),
+                };
                 Ok(Move::new_promotion(from, to, promotion)
-                    .ok_or(err!("Invalid promotion code"))?)
+                    .ok_or(NotationError::InvalidPromotion(code.to_string()))?)
             } else {
-                Err(err!(
-                    "Pawns can only be promoted on the last rank, remove '=...' from the notation"
-                ))
+                Err(MoveError::InvalidPromotion)
             }
         } else {
             if piece.is_kind(PieceKind::Pawn) && (to.rank() == 0 || to.rank() == 7) {
-                return Err(err!(
-                    "Pawns must be promoted on the last rank, add '=...' to the notation"
-                ));
+                return Err(MoveError::MissingPromotion);
             }
 
             Ok(Move::new_normal(from, to))
         }
     }
 
-    fn split_notation(&self, notation: &str) -> Result<Notation, NotationError> {
+    fn split_notation(&self, notation: &str) -> Result<Notation, MoveError> {
         match notation {
-            "00" => {
-                let color = self.which_color();
-                let rank = if color == PieceColor::White { 0 } else { 7 };
-                Ok(Notation {
-                    piece: PieceKind::King,
-                    from: Some(Square::new(4, rank).unwrap()),
-                    take: false,
-                    to: Square::new(6, rank).unwrap(),
-                    promotion: None,
-                })
-            }
-            "000" => {
-                let color = self.which_color();
-                let rank = if color == PieceColor::White { 0 } else { 7 };
+            "00" | "000" => {
+                let side = match notation {
+                    "00" => CastleSide::King,
+                    _ => CastleSide::Queen,
+                };
+                // Without the right, the standard squares are kept so that `perform` tells what is wrong
+                let rank = self.which_color().back_rank();
+                let mv = self.castling_move(side).unwrap_or(Move::new_normal(
+                    Square::new(4, rank).unwrap(),
+                    Square::new(side.king_target_file(), rank).unwrap(),
+                ));
                 Ok(Notation {
                     piece: PieceKind::King,
-                    from: Some(Square::new(4, rank).unwrap()),
+                    from: Some(mv.from),
                     take: false,
-                    to: Square::new(2, rank).unwrap(),
+                    to: mv.to,
                     promotion: None,
                 })
             }
@@ -149,41 +141,33 @@ impl Board {
 
                 let mut cur = 0;
 
-                if chars
-                    .first()
-                    .ok_or(err!("Piece code required"))?
-                    .is_uppercase()
-                {
+                if chars.first().ok_or(NotationError::Empty)?.is_uppercase() {
                     notation.piece =
                         PieceKind::from_notation(&chars[0..=0].iter().collect::<String>())
-                            .ok_or(err!("Invalid piece code {}", chars[0]))?;
+                            .ok_or(NotationError::InvalidPiece(chars[0]))?;
                     cur += 1
                 } else {
                     notation.piece = PieceKind::Pawn;
                 }
 
                 let mut first = None;
-                if let 'a'..='h' = *chars
-                    .get(cur)
-                    .ok_or(err!("At least one square code is required"))?
-                {
-                    chars.get(cur + 1).ok_or(err!("Incomplete square code"))?;
+                if let 'a'..='h' = *chars.get(cur).ok_or(NotationError::MissingTarget)? {
+                    chars
+                        .get(cur + 1)
+                        .ok_or(NotationError::BadSquare(chars[cur].to_string()))?;
 
                     if let Some('1'..='8') = chars.get(cur + 1) {
                         first = Some(
                             Square::from_notation(&chars[cur..=cur + 1].iter().collect::<String>())
-                                .ok_or(err!(
-                                    "Invalid square code {}",
-                                    chars[cur..=cur + 1].iter().collect::<String>()
+                                .ok_or(NotationError::BadSquare(
+                                    chars[cur..=cur + 1].iter().collect::<String>(),
                                 ))?,
                         );
                         cur += 2;
                     } else {
                         first = Some(
                             self.find_piece_shortcut(chars[cur], notation.piece)
-                                .ok_or(err!(
-                                "Shortcut square cannot be found, it either is ambiguous or does not exist"
-                            ))?,
+                                .ok_or(NotationError::UnknownFile(chars[cur]))?,
                         );
                         cur += 1;
                     }
@@ -196,23 +180,22 @@ impl Board {
 
                 let mut second = None;
                 if let Some('a'..='h') = chars.get(cur) {
-                    chars.get(cur + 1).ok_or(err!("Incomplete square code"))?;
+                    chars
+                        .get(cur + 1)
+                        .ok_or(NotationError::BadSquare(chars[cur].to_string()))?;
 
                     if let Some('1'..='8') = chars.get(cur + 1) {
                         second = Some(
                             Square::from_notation(&chars[cur..=cur + 1].iter().collect::<String>())
-                                .ok_or(err!(
-                                    "Invalid square code {}",
-                                    chars[cur..=cur + 1].iter().collect::<String>()
+                                .ok_or(NotationError::BadSquare(
+                                    chars[cur..=cur + 1].iter().collect::<String>(),
                                 ))?,
                         );
                         cur += 2;
                     } else {
                         second = Some(
                             self.find_piece_shortcut(chars[cur], notation.piece)
-                                .ok_or(err!(
-                                "Shortcut square cannot be found, it either is ambiguous or does not exist"
-                            ))?,
+                                .ok_or(NotationError::UnknownFile(chars[cur]))?,
                         );
                         cur += 1;
                     }
@@ -226,18 +209,17 @@ impl Board {
                         notation.to = first;
                     }
                 } else {
-                    notation.to = second.ok_or(err!("Target square is not specified"))?;
+                    notation.to = second.ok_or(NotationError::MissingTarget)?;
                 }
 
                 if let Some('=') = chars.get(cur) {
                     cur += 1;
-                    chars.get(cur).ok_or(err!("Promotion code is missing"))?;
+                    chars
+                        .get(cur)
+                        .ok_or(NotationError::InvalidPromotion(String::new()))?;
                     notation.promotion = Some(
                         PieceKind::from_notation(&chars[cur..=cur].iter().collect::<String>())
-                            .ok_or(err!(
-                                "Invalid promotion code {}",
-                                chars[cur..cur + 1].iter().collect::<String>()
-                            ))?,
+                            .ok_or(NotationError::InvalidPromotion(chars[cur].to_string()))?,
                     );
                     #[allow(unused_assignments)]
                     {
@@ -250,3 +232,31 @@ impl Board {
         }
     }
 }
+
+/// Brings the many ways of writing a move down to the forms `split_notation` and SAN matching understand
+fn normalize(notation: &str) -> String {
+    let mut notation = notation.split_whitespace().collect::<String>();
+    if let Some(stripped) = notation.strip_suffix("e.p.") {
+        notation = stripped.to_string();
+    }
+    let notation = notation.trim_end_matches(['+', '#', '!', '?']);
+
+    // Castling may be written with letters or zeros, with or without dashes
+    if !notation.is_empty() && notation.chars().all(|c| matches!(c, 'O' | 'o' | '0' | '-')) {
+        return notation.replace('-', "").replace(['O', 'o'], "0");
+    }
+
+    let mut notation = notation.replace('-', "");
+
+    // Promotions as in UCI, e.g. `e7e8q`, or without the '=', e.g. `e8Q`
+    let mut chars = notation.chars().rev();
+    if let (Some(last @ ('q' | 'r' | 'b' | 'n' | 'Q' | 'R' | 'B' | 'N')), Some('1'..='8')) =
+        (chars.next(), chars.next())
+    {
+        notation.pop();
+        notation.push('=');
+        notation.push(last.to_ascii_uppercase());
+    }
+
+    notation
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+impl Board {
+    /// Counts the leaf nodes of the legal move tree at the given depth
+    pub fn perft(&self, depth: usize) -> u64 {
+        self.clone().perft_in_place(depth)
+    }
+
+    /// Like `perft`, but splits the count by the first move
+    pub fn divide(&self, depth: usize) -> Vec<(Move, u64)> {
+        let mut board = self.clone();
+        let mut result = Vec::new();
+        if depth == 0 {
+            return result;
+        }
+
+        for mv in board.legal_moves() {
+            let undo = board.make_move(mv);
+            result.push((mv, board.perft_in_place(depth - 1)));
+            board.unmake_move(undo);
+        }
+        result
+    }
+
+    fn perft_in_place(&mut self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in moves {
+            let undo = self.make_move(mv);
+            nodes += self.perft_in_place(depth - 1);
+            self.unmake_move(undo);
+        }
+        nodes
+    }
+}
@@ -0,0 +1,193 @@
+use super::*;
+
+/// The Seven Tag Roster of a PGN game, apart from the result which comes from the board
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameInfo {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+}
+
+impl GameInfo {
+    /// A game played today
+    pub fn new(event: &str, white: &str, black: &str) -> Self {
+        GameInfo {
+            event: event.to_string(),
+            site: "TermiChess".to_string(),
+            date: today(),
+            round: "-".to_string(),
+            white: white.to_string(),
+            black: black.to_string(),
+        }
+    }
+}
+
+impl Status {
+    /// The result as written in PGN
+    pub fn to_pgn(&self) -> &'static str {
+        match self {
+            Status::Playing => "*",
+            Status::White => "1-0",
+            Status::Black => "0-1",
+            Status::Draw => "1/2-1/2",
+        }
+    }
+}
+
+impl Termination {
+    /// The value of the PGN Termination tag, which only tells normal endings from unusual ones
+    pub fn to_pgn(&self) -> &'static str {
+        match self {
+            Termination::Timeout => "time forfeit",
+            Termination::Abandonment => "abandoned",
+            _ => "normal",
+        }
+    }
+
+    /// The precise reason, written as a comment before the result
+    pub fn name(&self) -> &'static str {
+        match self {
+            Termination::Checkmate => "Checkmate",
+            Termination::Resignation => "Resignation",
+            Termination::Timeout => "Time forfeit",
+            Termination::Abandonment => "Game abandoned",
+            Termination::Stalemate => "Stalemate",
+            Termination::Agreement => "Draw by agreement",
+            Termination::Repetition => "Draw by repetition",
+            Termination::FiftyMoves => "Draw by the fifty-move rule",
+            Termination::InsufficientMaterial => "Draw by insufficient material",
+        }
+    }
+}
+
+/// The value of the PGN TimeControl tag, e.g. `40/5400+30:1800+30`, which has no way to write delays
+fn time_control_to_pgn(control: &TimeControl) -> String {
+    let stages: Vec<String> = control
+        .stages
+        .iter()
+        .map(|stage| {
+            let mut field = match stage.moves {
+                Some(moves) => format!("{}/{}", moves, stage.time.as_secs()),
+                None => stage.time.as_secs().to_string(),
+            };
+            if let Bonus::Increment(increment) = stage.bonus {
+                field.push_str(&format!("+{}", increment.as_secs()));
+            }
+            field
+        })
+        .collect();
+    stages.join(":")
+}
+
+impl Board {
+    /// Writes the whole game in PGN, replaying the moves from the starting position,
+    /// which fails when the FEN the game started from cannot be read
+    pub fn to_pgn(&self, info: &GameInfo) -> Result<String, FenError> {
+        let result = self.status.to_pgn();
+        let mut pgn = String::new();
+
+        for (name, value) in [
+            ("Event", &info.event),
+            ("Site", &info.site),
+            ("Date", &info.date),
+            ("Round", &info.round),
+            ("White", &info.white),
+            ("Black", &info.black),
+        ] {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape(value)));
+        }
+        pgn.push_str(&format!("[Result \"{}\"]\n", result));
+        if let Some(termination) = self.termination {
+            pgn.push_str(&format!("[Termination \"{}\"]\n", termination.to_pgn()));
+        }
+
+        if let Some(clock) = &self.clock {
+            pgn.push_str(&format!(
+                "[TimeControl \"{}\"]\n",
+                time_control_to_pgn(&clock.control)
+            ));
+        }
+
+        if self.variant == Variant::Chess960 {
+            pgn.push_str("[Variant \"Chess960\"]\n");
+        }
+
+        // A Chess960 game always gives its starting position, even the standard one
+        let setup = match (&self.setup, self.variant) {
+            (None, Variant::Chess960) => Some(START_FEN.to_string()),
+            (setup, _) => setup.clone(),
+        };
+        let mut board = match &setup {
+            Some(fen) => {
+                pgn.push_str("[SetUp \"1\"]\n");
+                pgn.push_str(&format!("[FEN \"{}\"]\n", fen));
+                Board::from_fen(fen)?
+            }
+            None => Board::new(),
+        };
+        board.variant = self.variant;
+        pgn.push('\n');
+
+        let mut tokens = Vec::new();
+        for (i, &mv) in self.moves.iter().enumerate() {
+            let number = board.turn.div_ceil(2);
+            if board.which_color() == PieceColor::White {
+                tokens.push(format!("{}.", number));
+            } else if i == 0 {
+                tokens.push(format!("{}...", number));
+            }
+            tokens.push(board.to_san(mv));
+            board.make_move(mv);
+        }
+        if let Some(termination) = self.termination {
+            tokens.push(format!("{{{}}}", termination.name()));
+        }
+        tokens.push(result.to_string());
+
+        // Lines are kept under 80 characters, as the PGN standard recommends
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + token.len() + 1 > 79 {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        pgn.push_str(&line);
+        pgn.push('\n');
+
+        Ok(pgn)
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Today's date as `YYYY.MM.DD`
+fn today() -> String {
+    let days = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86400)
+        .unwrap_or_default() as i64;
+
+    // Converts days since 1970-01-01 to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!("{:04}.{:02}.{:02}", year, month, day)
+}
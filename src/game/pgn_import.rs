@@ -0,0 +1,300 @@
+use super::*;
+
+message_error! {
+    pub struct PgnError;
+}
+
+/// A move of the movetext together with what was written around it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PgnMove {
+    pub san: String,
+    // Numeric annotation glyphs, with `!` and `?` suffixes converted to their `$n` equivalent
+    pub nags: Vec<u8>,
+    pub comments: Vec<String>,
+    // Alternatives to this move, each starting from the position before it
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+/// A game read from a PGN file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PgnGame {
+    pub tags: Vec<(String, String)>,
+    // Comment before the first move
+    pub comments: Vec<String>,
+    pub moves: Vec<PgnMove>,
+    pub result: String,
+}
+
+/// Reads every game of a PGN file
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut games = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut game = PgnGame::default();
+    // The innermost variation is at the top, the main line at the bottom
+    let mut lines: Vec<Vec<PgnMove>> = vec![Vec::new()];
+    let mut line_start = true;
+    let mut started = false;
+
+    while let Some(c) = chars.next() {
+        let at_line_start = line_start;
+        line_start = c == '\n';
+
+        match c {
+            // Escaped lines are meant for other programs
+            '%' if at_line_start => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line_start = true;
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            '[' => {
+                if started {
+                    return Err(err!(
+                        PgnError,
+                        "Tag found inside the movetext of game {}",
+                        games.len() + 1
+                    ));
+                }
+                let mut tag = String::new();
+                let mut in_string = false;
+                let mut escaped = false;
+                loop {
+                    let c = chars.next().ok_or(err!(
+                        PgnError,
+                        "Unterminated tag in game {}",
+                        games.len() + 1
+                    ))?;
+                    match c {
+                        _ if escaped => {
+                            tag.push(c);
+                            escaped = false;
+                        }
+                        '\\' if in_string => escaped = true,
+                        '"' => {
+                            in_string = !in_string;
+                            tag.push(c);
+                        }
+                        ']' if !in_string => break,
+                        _ => tag.push(c),
+                    }
+                }
+                let (name, value) = tag.trim().split_once(char::is_whitespace).ok_or(err!(
+                    PgnError,
+                    "Invalid tag [{}]",
+                    tag
+                ))?;
+                let value = value
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .ok_or(err!(PgnError, "Tag {} has no quoted value", name))?;
+                game.tags.push((name.to_string(), value.to_string()));
+            }
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => {
+                            return Err(err!(
+                                PgnError,
+                                "Unterminated comment in game {}",
+                                games.len() + 1
+                            ))
+                        }
+                    }
+                }
+                push_comment(&mut game, &mut lines, comment);
+            }
+            ';' => {
+                let mut comment = String::new();
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line_start = true;
+                        break;
+                    }
+                    comment.push(c);
+                }
+                push_comment(&mut game, &mut lines, comment);
+            }
+            '(' => {
+                if lines.last().is_none_or(|line| line.is_empty()) {
+                    return Err(err!(
+                        PgnError,
+                        "Variation without a move to replace in game {}",
+                        games.len() + 1
+                    ));
+                }
+                lines.push(Vec::new());
+            }
+            ')' => {
+                if lines.len() < 2 {
+                    return Err(err!(PgnError, "Unmatched ')' in game {}", games.len() + 1));
+                }
+                let variation = lines.pop().unwrap();
+                if let Some(mv) = lines.last_mut().and_then(|line| line.last_mut()) {
+                    mv.variations.push(variation);
+                }
+            }
+            '$' => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    number.push(c);
+                    chars.next();
+                }
+                let nag = number
+                    .parse()
+                    .map_err(|_| err!(PgnError, "Invalid annotation ${}", number))?;
+                push_nag(&mut lines, nag)?;
+            }
+            _ => {
+                let mut token = c.to_string();
+                while let Some(&c) = chars
+                    .peek()
+                    .filter(|c| !c.is_whitespace() && !"{}()[];$".contains(**c))
+                {
+                    token.push(c);
+                    chars.next();
+                }
+                started = true;
+
+                if matches!(token.as_str(), "1-0" | "0-1" | "1/2-1/2" | "*") {
+                    if lines.len() > 1 {
+                        return Err(err!(
+                            PgnError,
+                            "Unterminated variation in game {}",
+                            games.len() + 1
+                        ));
+                    }
+                    game.moves = lines.pop().unwrap();
+                    game.result = token;
+                    games.push(std::mem::take(&mut game));
+                    lines.push(Vec::new());
+                    started = false;
+                    continue;
+                }
+
+                // Move numbers such as `12.` or `12...` may be glued to the move itself
+                let token = match token.rsplit_once('.') {
+                    Some((number, san)) if number.starts_with(|c: char| c.is_ascii_digit()) => san,
+                    _ if token.chars().all(|c| c.is_ascii_digit()) => "",
+                    _ => &token,
+                };
+                let san = token.trim_end_matches(['!', '?']);
+                let suffix = &token[san.len()..];
+                if !san.is_empty() {
+                    lines.last_mut().unwrap().push(PgnMove {
+                        san: san.to_string(),
+                        ..Default::default()
+                    });
+                }
+                if !suffix.is_empty() {
+                    let nag = match suffix {
+                        "!" => 1,
+                        "?" => 2,
+                        "!!" => 3,
+                        "??" => 4,
+                        "!?" => 5,
+                        "?!" => 6,
+                        _ => return Err(err!(PgnError, "Invalid annotation {}", suffix)),
+                    };
+                    push_nag(&mut lines, nag)?;
+                }
+            }
+        }
+    }
+
+    // A missing result at the end of the file is tolerated
+    if started || !game.tags.is_empty() {
+        if lines.len() > 1 {
+            return Err(err!(
+                PgnError,
+                "Unterminated variation in game {}",
+                games.len() + 1
+            ));
+        }
+        game.moves = lines.pop().unwrap();
+        game.result = "*".to_string();
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+fn push_comment(game: &mut PgnGame, lines: &mut [Vec<PgnMove>], comment: String) {
+    let comment = comment.split_whitespace().collect::<Vec<_>>().join(" ");
+    match lines.last_mut().and_then(|line| line.last_mut()) {
+        Some(mv) => mv.comments.push(comment),
+        None => game.comments.push(comment),
+    }
+}
+
+fn push_nag(lines: &mut [Vec<PgnMove>], nag: u8) -> Result<(), PgnError> {
+    lines
+        .last_mut()
+        .and_then(|line| line.last_mut())
+        .ok_or(err!(PgnError, "Annotation ${} without a move", nag))?
+        .nags
+        .push(nag);
+    Ok(())
+}
+
+impl PgnGame {
+    /// The value of a tag, if present
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The position the game starts from, taken from the FEN tag when there is one
+    pub fn start(&self) -> Result<Board, PgnError> {
+        let mut board = match self.tag("FEN") {
+            Some(fen) => {
+                Board::from_fen(fen).map_err(|e| err!(PgnError, "Invalid FEN tag: {}", e))?
+            }
+            None => Board::new(),
+        };
+        // Other programs also write Fischer Random under its older names
+        if let Some(variant) = self.tag("Variant") {
+            let variant = variant.to_ascii_lowercase().replace([' ', '-'], "");
+            if matches!(
+                variant.as_str(),
+                "chess960" | "fischerandom" | "fischerrandom" | "960"
+            ) {
+                board
+                    .set_variant(Variant::Chess960)
+                    .map_err(|_| err!(PgnError, "Invalid Chess960 position"))?;
+            }
+        }
+        Ok(board)
+    }
+
+    /// Plays the main line, returning every position from the start to the end of the game
+    pub fn replay(&self) -> Result<Vec<Board>, PgnError> {
+        let mut boards = vec![self.start()?];
+        for mv in &self.moves {
+            let board = boards.last().unwrap();
+            let number = board.turn.div_ceil(2);
+            let next = board
+                .translate(&mv.san)
+                .and_then(|translated| board.perform(translated))
+                .map_err(|e| {
+                    err!(
+                        PgnError,
+                        "Illegal move {} at move {}: {}",
+                        mv.san,
+                        number,
+                        e
+                    )
+                })?;
+            boards.push(next);
+        }
+        Ok(boards)
+    }
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PieceKind {
     Empty,
     King,
@@ -11,13 +11,13 @@ pub enum PieceKind {
     Pawn,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PieceColor {
     White,
     Black,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Piece {
     pub kind: PieceKind,
     pub color: PieceColor,
@@ -63,6 +63,14 @@ impl PieceColor {
         }
     }
 
+    /// The rank the pieces of this color start on
+    pub fn back_rank(&self) -> usize {
+        match self {
+            PieceColor::White => 0,
+            PieceColor::Black => 7,
+        }
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             PieceColor::White => "White",
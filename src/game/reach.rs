@@ -1,181 +1,59 @@
 use super::*;
 
 impl Board {
-    pub fn reachable(&self, square: Square) -> HashSet<Square> {
+    /// The squares the piece on `square` can move to, regardless of whether its own king is left in check
+    pub fn reachable(&self, square: Square) -> Bitboard {
         let piece = self.get(square);
 
         match piece.kind {
-            PieceKind::Empty => HashSet::new(),
+            PieceKind::Empty => Bitboard::EMPTY,
             PieceKind::King => self.reachable_king(square, piece),
-            PieceKind::Queen => self.reachable_queen(square, piece),
-            PieceKind::Bishop => self.reachable_bishop(square, piece),
-            PieceKind::Knight => self.reachable_knight(square, piece),
-            PieceKind::Rook => self.reachable_rook(square, piece),
             PieceKind::Pawn => self.reachable_pawn(square, piece),
+            _ => self.attacks_from(square, piece, self.occupied()) & !self.pieces_of(piece.color),
         }
     }
 
-    fn reachable_king(&self, square: Square, piece: Piece) -> HashSet<Square> {
-        let mut squares = HashSet::new();
-
-        for file in -1..=1 {
-            for rank in -1..=1 {
-                if file == 0 && rank == 0 {
-                    continue;
-                }
-
-                if let Some(target) = square.offset(file, rank) {
-                    if self.get(target).is_replaceable(piece.color) {
-                        squares.insert(target);
-                    }
-                }
-            }
-        }
-
-        if piece.previous == 0 && piece.color == self.which_color() {
-            if let Some(_rook) = self.get_kingside_rook() {
-                let mut ok = true;
-
-                for disp in 0..=3 {
-                    if let Some(square) = square.offset(disp, 0) {
-                        if self.is_threatened(square) {
-                            ok = false;
-                            break;
-                        }
-                    }
-                }
-                for disp in 1..=2 {
-                    if let Some(square) = square.offset(disp, 0) {
-                        if !self.get(square).is_empty() {
-                            ok = false;
-                            break;
-                        }
-                    }
-                }
-
-                if ok {
-                    squares.insert(Square::new(6, square.rank()).unwrap());
-                }
-            }
-
-            if let Some(_rook) = self.get_queenside_rook() {
-                let mut ok = true;
-
-                for disp in 0..=4 {
-                    if let Some(square) = square.offset(-disp, 0) {
-                        if self.is_threatened(square) {
-                            ok = false;
-                            break;
-                        }
-                    }
-                }
-                for disp in 1..=3 {
-                    if let Some(square) = square.offset(-disp, 0) {
-                        if !self.get(square).is_empty() {
-                            ok = false;
-                            break;
-                        }
-                    }
-                }
-
-                if ok {
-                    squares.insert(Square::new(2, square.rank()).unwrap());
-                }
+    /// The squares the piece attacks, given which squares are occupied
+    pub fn attacks_from(&self, square: Square, piece: Piece, occupied: Bitboard) -> Bitboard {
+        match piece.kind {
+            PieceKind::Empty => Bitboard::EMPTY,
+            PieceKind::King => Bitboard::king_attacks(square),
+            PieceKind::Queen => {
+                Bitboard::rook_attacks(square, occupied)
+                    | Bitboard::bishop_attacks(square, occupied)
             }
+            PieceKind::Bishop => Bitboard::bishop_attacks(square, occupied),
+            PieceKind::Knight => Bitboard::knight_attacks(square),
+            PieceKind::Rook => Bitboard::rook_attacks(square, occupied),
+            PieceKind::Pawn => Bitboard::pawn_attacks(square, piece.color),
         }
-
-        squares
-    }
-
-    fn get_kingside_rook(&self) -> Option<Piece> {
-        let color = self.which_color();
-        let rank = match color {
-            PieceColor::White => 0,
-            PieceColor::Black => 7,
-        };
-
-        let rook = Square::new(7, rank)?;
-        let piece = self.get(rook);
-
-        if piece.kind != PieceKind::Rook || piece.previous != 0 {
-            return None;
-        }
-
-        Some(piece)
-    }
-
-    fn get_queenside_rook(&self) -> Option<Piece> {
-        let color = self.which_color();
-        let rank = match color {
-            PieceColor::White => 0,
-            PieceColor::Black => 7,
-        };
-
-        let rook = Square::new(0, rank)?;
-        let piece = self.get(rook);
-
-        if piece.kind != PieceKind::Rook || piece.previous != 0 {
-            return None;
-        }
-
-        Some(piece)
     }
 
-    fn reachable_queen(&self, square: Square, piece: Piece) -> HashSet<Square> {
-        let mut squares = HashSet::new();
-
-        squares.extend(self.reachable_bishop(square, piece));
-        squares.extend(self.reachable_rook(square, piece));
-
-        squares
+    /// The pieces of `color` attacking the square, given which squares are occupied
+    pub fn attackers(&self, square: Square, color: PieceColor, occupied: Bitboard) -> Bitboard {
+        let queens = self.pieces(PieceKind::Queen, color);
+        let attackers = (Bitboard::knight_attacks(square) & self.pieces(PieceKind::Knight, color))
+            | (Bitboard::king_attacks(square) & self.pieces(PieceKind::King, color))
+            | (Bitboard::pawn_attacks(square, color.opposite())
+                & self.pieces(PieceKind::Pawn, color))
+            | (Bitboard::rook_attacks(square, occupied)
+                & (self.pieces(PieceKind::Rook, color) | queens))
+            | (Bitboard::bishop_attacks(square, occupied)
+                & (self.pieces(PieceKind::Bishop, color) | queens));
+        attackers & occupied
     }
 
-    fn reachable_bishop(&self, square: Square, piece: Piece) -> HashSet<Square> {
-        let mut squares = HashSet::new();
-
-        for file in -1..=1 {
-            for rank in -1..=1 {
-                if file == 0 || rank == 0 {
-                    continue;
-                }
-
-                for distance in 1..=7 {
-                    if let Some(target) = square.offset(file * distance, rank * distance) {
-                        if self.get(target).is_friend(piece.color) {
-                            break;
-                        }
-                        squares.insert(target);
-                        if self.get(target).is_enemy(piece.color) {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                }
-            }
-        }
-
-        squares
+    pub fn is_attacked(&self, square: Square, color: PieceColor) -> bool {
+        !self.attackers(square, color, self.occupied()).is_empty()
     }
 
-    fn reachable_knight(&self, square: Square, piece: Piece) -> HashSet<Square> {
-        let mut squares = HashSet::new();
-
-        let offsets = [
-            (-2, -1),
-            (-2, 1),
-            (-1, -2),
-            (-1, 2),
-            (1, -2),
-            (1, 2),
-            (2, -1),
-            (2, 1),
-        ];
+    fn reachable_king(&self, square: Square, piece: Piece) -> Bitboard {
+        let mut squares = Bitboard::king_attacks(square) & !self.pieces_of(piece.color);
 
-        for &(file_offset, rank_offset) in &offsets {
-            if let Some(target) = square.offset(file_offset, rank_offset) {
-                if self.get(target).is_replaceable(piece.color) {
-                    squares.insert(target);
+        if piece.color == self.which_color() {
+            for side in CastleSide::ALL {
+                if self.may_castle(side) {
+                    squares.insert(self.castling_move(side).unwrap().to);
                 }
             }
         }
@@ -183,83 +61,42 @@ impl Board {
         squares
     }
 
-    fn reachable_rook(&self, square: Square, piece: Piece) -> HashSet<Square> {
-        let mut squares = HashSet::new();
-
-        for file in -1..=1 {
-            for rank in -1..=1 {
-                if file != 0 && rank != 0 {
-                    continue;
-                }
-
-                for distance in 1..=7 {
-                    if let Some(target) = square.offset(file * distance, rank * distance) {
-                        if self.get(target).is_friend(piece.color) {
-                            break;
-                        }
-                        squares.insert(target);
-                        if self.get(target).is_enemy(piece.color) {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                }
-            }
-        }
-
-        squares
+    /// Whether the side to move can castle right now, apart from where its king ends up
+    pub(super) fn may_castle(&self, side: CastleSide) -> bool {
+        let enemy = self.which_color().opposite();
+        self.castling_squares(side).is_some_and(|(empty, safe)| {
+            (empty & self.occupied()).is_empty()
+                && safe.iter().all(|square| !self.is_attacked(square, enemy))
+        })
     }
 
-    fn reachable_pawn(&self, square: Square, piece: Piece) -> HashSet<Square> {
-        let mut squares = HashSet::new();
+    fn reachable_pawn(&self, square: Square, piece: Piece) -> Bitboard {
+        let mut squares = Bitboard::EMPTY;
 
-        let direction = match piece.color {
-            PieceColor::White => 1,
-            PieceColor::Black => -1,
+        let (direction, start) = match piece.color {
+            PieceColor::White => (1, 1),
+            PieceColor::Black => (-1, 6),
         };
 
         if let Some(target) = square.offset(0, direction) {
             if self.get(target).is_empty() {
                 squares.insert(target);
-            }
-        }
-
-        if let Some(target) = square.offset(1, direction) {
-            if self.get(target).is_enemy(piece.color) {
-                squares.insert(target);
-            }
-        }
-
-        if let Some(target) = square.offset(-1, direction) {
-            if self.get(target).is_enemy(piece.color) {
-                squares.insert(target);
-            }
-        }
 
-        if piece.previous == 0 {
-            if let (Some(target), Some(passage)) =
-                (square.offset(0, direction * 2), square.offset(0, direction))
-            {
-                if self.get(target).is_empty() && self.get(passage).is_empty() {
-                    squares.insert(target);
+                if square.rank() == start {
+                    if let Some(target) = square.offset(0, direction * 2) {
+                        if self.get(target).is_empty() {
+                            squares.insert(target);
+                        }
+                    }
                 }
             }
         }
 
+        let mut targets = self.pieces_of(piece.color.opposite());
         if let Some(target) = self.possible_en_passant() {
-            if let Some(pawn) = target.offset(1, -direction) {
-                if pawn == square {
-                    squares.insert(target);
-                }
-            }
-
-            if let Some(pawn) = target.offset(-1, -direction) {
-                if pawn == square {
-                    squares.insert(target);
-                }
-            }
+            targets.insert(target);
         }
+        squares |= Bitboard::pawn_attacks(square, piece.color) & targets;
 
         squares
     }
@@ -0,0 +1,82 @@
+use super::*;
+
+impl Board {
+    /// Writes a legal move in Standard Algebraic Notation, e.g. `Nbd7`, `exd6`, `e8=Q+` or `O-O-O#`
+    pub fn to_san(&self, mv: Move) -> String {
+        let mut san = self.san_body(mv);
+
+        let mut board = self.clone();
+        board.make_move(mv);
+        if board.is_check() {
+            san.push(if board.legal_moves().is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+
+        san
+    }
+
+    /// The SAN of a move without the check or checkmate suffix
+    pub(super) fn san_body(&self, mv: Move) -> String {
+        let piece = self.get(mv.from);
+        let mut san = String::new();
+
+        if let Some(side) = self.castle_side(mv) {
+            san.push_str(match side {
+                CastleSide::King => "O-O",
+                CastleSide::Queen => "O-O-O",
+            });
+        } else {
+            let is_capture = !self.get(mv.to).is_empty()
+                || (piece.is_kind(PieceKind::Pawn) && mv.from.file() != mv.to.file());
+
+            if piece.is_kind(PieceKind::Pawn) {
+                if is_capture {
+                    san.push((b'a' + mv.from.file() as u8) as char);
+                }
+            } else {
+                san.push_str(piece.kind.to_notation().unwrap_or_default());
+                san.push_str(&self.disambiguate(mv, piece));
+            }
+
+            if is_capture {
+                san.push('x');
+            }
+            san.push_str(&mv.to.to_notation());
+
+            if let Some(promotion) = mv.promotion.and_then(|kind| kind.to_notation()) {
+                san.push('=');
+                san.push_str(promotion);
+            }
+        }
+
+        san
+    }
+
+    /// The shortest part of the source square that tells the move apart from the same kind of piece moving to the same square
+    fn disambiguate(&self, mv: Move, piece: Piece) -> String {
+        let others = self
+            .legal_moves()
+            .into_iter()
+            .filter(|other| {
+                other.to == mv.to
+                    && other.from != mv.from
+                    && self.get(other.from).is_kind(piece.kind)
+            })
+            .map(|other| other.from)
+            .collect::<Vec<_>>();
+
+        let square = mv.from.to_notation();
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|other| other.file() != mv.from.file()) {
+            square[0..1].to_string()
+        } else if others.iter().all(|other| other.rank() != mv.from.rank()) {
+            square[1..2].to_string()
+        } else {
+            square
+        }
+    }
+}
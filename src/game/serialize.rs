@@ -32,7 +32,7 @@ impl Piece {
             let mut chars = s.chars();
             let kind = chars.next().unwrap();
             let color = chars.next().unwrap();
-            let previous: usize = chars.collect::<String>().parse().unwrap();
+            let previous: usize = chars.collect::<String>().parse().ok()?;
 
             let kind = match kind {
                 'P' => PieceKind::Pawn,
@@ -58,19 +58,24 @@ impl Piece {
 }
 
 impl Move {
+    /// Writes the move in UCI notation, e.g. `e2e4` or `e7e8q`
     pub fn serialize(&self) -> String {
-        format!("{}{}", self.from.to_notation(), self.to.to_notation())
+        self.to_string()
     }
 
     pub fn deserialize(s: String) -> Option<Self> {
-        if s.len() != 4 {
-            None
-        } else {
-            Some(Self {
-                from: Square::from_notation(&s[0..=1])?,
-                to: Square::from_notation(&s[2..=3])?,
-                promotion: None,
-            })
+        if !s.is_ascii() {
+            return None;
+        }
+        let from = Square::from_notation(s.get(0..2)?)?;
+        let to = Square::from_notation(s.get(2..4)?)?;
+        match &s[4..] {
+            "" => Some(Move::new_normal(from, to)),
+            promotion => Move::new_promotion(
+                from,
+                to,
+                PieceKind::from_notation(&promotion.to_ascii_uppercase())?,
+            ),
         }
     }
 }
@@ -100,37 +105,234 @@ impl Status {
     }
 }
 
-impl Board {
+impl Termination {
     pub fn serialize(&self) -> String {
-        let squares = self
-            .squares
-            .iter()
-            .map(|rank| {
-                rank.iter()
-                    .map(|piece| piece.serialize())
-                    .collect::<String>()
-            })
-            .collect::<String>();
-        let moves = self
-            .moves
-            .iter()
-            .map(|mv| mv.serialize())
-            .collect::<String>();
-        let status = self.status.serialize();
+        match *self {
+            Termination::Checkmate => "checkmate",
+            Termination::Resignation => "resignation",
+            Termination::Timeout => "timeout",
+            Termination::Abandonment => "abandonment",
+            Termination::Stalemate => "stalemate",
+            Termination::Agreement => "agreement",
+            Termination::Repetition => "repetition",
+            Termination::FiftyMoves => "fifty-moves",
+            Termination::InsufficientMaterial => "insufficient-material",
+        }
+        .to_string()
+    }
+
+    pub fn deserialize(s: String) -> Option<Self> {
+        Some(match s.as_str() {
+            "checkmate" => Termination::Checkmate,
+            "resignation" => Termination::Resignation,
+            "timeout" => Termination::Timeout,
+            "abandonment" => Termination::Abandonment,
+            "stalemate" => Termination::Stalemate,
+            "agreement" => Termination::Agreement,
+            "repetition" => Termination::Repetition,
+            "fifty-moves" => Termination::FiftyMoves,
+            "insufficient-material" => Termination::InsufficientMaterial,
+            _ => return None,
+        })
+    }
+}
+
+impl Variant {
+    pub fn serialize(&self) -> String {
+        match *self {
+            Variant::Standard => "standard",
+            Variant::Chess960 => "chess960",
+        }
+        .to_string()
+    }
+
+    pub fn deserialize(s: String) -> Option<Self> {
+        Some(match s.as_str() {
+            "standard" => Variant::Standard,
+            "chess960" => Variant::Chess960,
+            _ => return None,
+        })
+    }
+}
+
+/// The version written by `Board::serialize`, boards without a version come from older releases
+pub const SERIALIZE_VERSION: u32 = 4;
+
+/// Everything needed to restore a board exactly, shared by the text and the JSON format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardRecord {
+    pub version: u32,
+    pub variant: Variant,
+    // The position the game started from, in FEN
+    pub start: String,
+    // Every move played since, in UCI notation
+    pub moves: Vec<String>,
+    // The current position in FEN, checked against the replayed moves
+    pub fen: String,
+    pub status: Status,
+    // Why the game ended, none while it goes on
+    pub termination: Option<Termination>,
+    pub draw_offer: bool,
+}
+
+impl From<Board> for BoardRecord {
+    fn from(board: Board) -> Self {
+        board.to_record()
+    }
+}
+
+impl TryFrom<BoardRecord> for Board {
+    type Error = RecordError;
 
+    fn try_from(record: BoardRecord) -> Result<Self, Self::Error> {
+        Board::from_record(&record)
+    }
+}
+
+impl Board {
+    pub fn to_record(&self) -> BoardRecord {
+        BoardRecord {
+            version: SERIALIZE_VERSION,
+            variant: self.variant,
+            start: self.setup.clone().unwrap_or(START_FEN.to_string()),
+            moves: self.moves.iter().map(Move::serialize).collect(),
+            fen: self.position_fen(),
+            status: self.status,
+            termination: self.termination,
+            draw_offer: self.draw_offer,
+        }
+    }
+
+    /// The FEN of the position on the board, without the turn a pending draw offer passes to the opponent
+    fn position_fen(&self) -> String {
+        if self.draw_offer {
+            let mut board = self.clone();
+            board.turn -= 1;
+            board.to_fen()
+        } else {
+            self.to_fen()
+        }
+    }
+
+    /// Restores a board by replaying its moves, so the position history comes back as well
+    pub fn from_record(record: &BoardRecord) -> Result<Self, RecordError> {
+        if record.version != SERIALIZE_VERSION {
+            return Err(RecordError::UnsupportedVersion(record.version));
+        }
+
+        let mut board = Board::from_fen(&record.start)?;
+        board
+            .set_variant(record.variant)
+            .map_err(RecordError::Position)?;
+        for notation in &record.moves {
+            let mv = Move::deserialize(notation.clone())
+                .ok_or(RecordError::InvalidMove(notation.clone()))?;
+            if !board.is_legal(mv) {
+                return Err(RecordError::IllegalMove(notation.clone()));
+            }
+            board.make_move(mv);
+        }
+        if board.to_fen() != record.fen {
+            return Err(RecordError::PositionMismatch(record.fen.clone()));
+        }
+        if record.draw_offer {
+            board.turn += 1;
+        }
+
+        board.update_mate();
+        board.status = record.status;
+        board.termination = record.termination;
+        board.draw_offer = record.draw_offer;
+
+        Ok(board)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, RecordError> {
+        let record: BoardRecord =
+            serde_json::from_str(s).map_err(|e| RecordError::Malformed(e.to_string()))?;
+        Board::from_record(&record)
+    }
+
+    /// Writes the board as `v4;<variant>;<start FEN>;<UCI moves>;<current FEN>;<status>;<termination>;<draw offer>`,
+    /// where the termination is `-` while the game goes on
+    pub fn serialize(&self) -> String {
+        let record = self.to_record();
         format!(
-            "{}/{}/{}/{}/{}",
-            squares,
-            moves,
-            self.turn,
-            status,
-            if self.draw_offer { 't' } else { 'f' }
+            "v{};{};{};{};{};{};{};{}",
+            record.version,
+            record.variant.serialize(),
+            record.start,
+            record.moves.join(" "),
+            record.fen,
+            record.status.serialize(),
+            record
+                .termination
+                .map_or("-".to_string(), |termination| termination.serialize()),
+            if record.draw_offer { 't' } else { 'f' }
         )
     }
 
-    pub fn deserialize(s: String) -> Option<Self> {
+    /// Reads a board written by `serialize`, or by an older release in the `/`-separated format
+    pub fn deserialize(s: String) -> Result<Self, RecordError> {
+        let Some(rest) = s.strip_prefix('v') else {
+            return Self::deserialize_legacy(s);
+        };
+
+        let parts = rest.split(';').collect::<Vec<_>>();
+        let [version, variant, start, moves, fen, status, termination, draw_offer] = parts[..]
+        else {
+            return Err(RecordError::Malformed(
+                "some of its fields are missing".to_string(),
+            ));
+        };
+        Board::from_record(&BoardRecord {
+            version: version.parse().map_err(|_| {
+                RecordError::Malformed(format!("the version {} is not a number", version))
+            })?,
+            variant: Variant::deserialize(variant.to_string())
+                .ok_or(RecordError::UnknownVariant(variant.to_string()))?,
+            start: start.to_string(),
+            moves: moves.split_whitespace().map(str::to_string).collect(),
+            fen: fen.to_string(),
+            status: Status::deserialize(status.to_string())
+                .ok_or(RecordError::InvalidStatus(status.to_string()))?,
+            termination: match termination {
+                "-" => None,
+                termination => Some(
+                    Termination::deserialize(termination.to_string())
+                        .ok_or(RecordError::UnknownTermination(termination.to_string()))?,
+                ),
+            },
+            draw_offer: match draw_offer {
+                "t" => true,
+                "f" => false,
+                _ => return Err(RecordError::InvalidDrawOffer(draw_offer.to_string())),
+            },
+        })
+    }
+
+    fn deserialize_legacy(s: String) -> Result<Self, RecordError> {
+        let mut board = Self::parse_legacy(s).ok_or(RecordError::Malformed(
+            "it is in no format this release can read".to_string(),
+        ))?;
+        board.validate().map_err(RecordError::Position)?;
+
+        board.update();
+        board.history = vec![board.hash()];
+        board.update_mate();
+
+        Ok(board)
+    }
+
+    /// Reads the fields of the `/`-separated format, leaving the position unchecked
+    fn parse_legacy(s: String) -> Option<Self> {
         let mut board = Self::new();
 
+        // The squares, the moves, the turn, the status and the draw offer
         let parts: Vec<String> = s.split('/').map(|v| v.to_string()).collect();
         let mut iter = parts.iter();
         if parts.len() != 5 {
@@ -161,7 +363,7 @@ impl Board {
                 }
             }
 
-            board.squares[file][rank] = Piece::deserialize(str)?;
+            board.set(Square::new(file, rank)?, Piece::deserialize(str)?);
 
             rank += 1;
             if rank == 8 {
@@ -170,7 +372,7 @@ impl Board {
             }
         }
 
-        if moves.len() % 4 != 0 {
+        if !moves.len().is_multiple_of(4) {
             return None;
         }
         let count = moves.len() / 4;
@@ -182,10 +384,40 @@ impl Board {
         }
 
         board.status = Status::deserialize(status.clone())?;
-
-        board.update();
-        board.update_mate();
+        board.restore_legacy_state();
 
         Some(board)
     }
+
+    /// Recovers castling rights and en passant from the pieces and moves, which the old format leaves out
+    fn restore_legacy_state(&mut self) {
+        self.castling = CastlingRights::none();
+        for color in [PieceColor::White, PieceColor::Black] {
+            let rank = color.back_rank();
+            let king = self.get(Square::new(4, rank).unwrap());
+            for side in CastleSide::ALL {
+                let rook = self.get(Square::new(side.rook_file(), rank).unwrap());
+                self.castling.set(
+                    color,
+                    side,
+                    king.is_friend(color)
+                        && king.is_kind(PieceKind::King)
+                        && king.previous == 0
+                        && rook.is_friend(color)
+                        && rook.is_kind(PieceKind::Rook)
+                        && rook.previous == 0,
+                );
+            }
+        }
+
+        self.en_passant = self.moves.last().and_then(|last| {
+            let is_pawn = self.get(last.to).is_kind(PieceKind::Pawn);
+            match (last.from.rank(), last.to.rank()) {
+                (1, 3) | (6, 4) if is_pawn => {
+                    Square::new(last.to.file(), (last.from.rank() + last.to.rank()) / 2)
+                }
+                _ => None,
+            }
+        });
+    }
 }
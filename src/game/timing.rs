@@ -0,0 +1,68 @@
+use super::*;
+use std::time::Instant;
+
+impl Board {
+    /// Plays the game on the clock, which starts on the next `sync_clock`
+    pub fn set_time_control(&mut self, control: TimeControl) {
+        self.clock = Some(Clock::new(control));
+    }
+
+    /// Runs the clock of the side to move, pressing it for the side that has just moved,
+    /// e.g. after a move or a draw offer, and stops it once the game is over
+    pub fn sync_clock(&mut self) {
+        let now = Instant::now();
+        let white = self.which_color() == PieceColor::White;
+        let Some(clock) = self.clock.as_mut() else {
+            return;
+        };
+
+        if self.status != Status::Playing {
+            clock.stop(now);
+        } else if clock.running() == Some(!white) {
+            if !clock.press(now) {
+                self.flag(self.which_color().opposite());
+            }
+        } else if clock.running().is_none() {
+            clock.start(white, now);
+        }
+    }
+
+    /// Ends the game if the side to move has run out of time, and tells whether it has
+    pub fn check_flag(&mut self) -> bool {
+        let color = self.which_color();
+        let is_flagged = self
+            .clock
+            .as_ref()
+            .is_some_and(|clock| clock.is_flagged(color == PieceColor::White, Instant::now()));
+        if is_flagged {
+            self.flag(color);
+        }
+        is_flagged
+    }
+
+    /// Ends the game on time against `color`, drawn when the opponent could never checkmate
+    pub fn flag(&mut self, color: PieceColor) {
+        if let Some(clock) = self.clock.as_mut() {
+            clock.stop(Instant::now());
+        }
+        let winner = color.opposite();
+        if self.can_checkmate(winner) {
+            self.finish(Some(winner), Termination::Timeout);
+        } else {
+            self.finish(None, Termination::Timeout);
+        }
+    }
+
+    /// Whether `color` has the material to checkmate by any series of legal moves
+    pub fn can_checkmate(&self, color: PieceColor) -> bool {
+        let own = self.pieces_of(color);
+        let minors = (self.pieces[PieceKind::Knight as usize]
+            | self.pieces[PieceKind::Bishop as usize])
+            & own;
+        // A lone king never mates, nor does a single minor piece against a lone king
+        let is_lone_minor = own.count() == 2
+            && minors.count() == 1
+            && self.pieces_of(color.opposite()).count() == 1;
+        own.count() > 1 && !is_lone_minor
+    }
+}
@@ -0,0 +1,169 @@
+use super::*;
+
+/// Something that makes a position impossible to reach or to play on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PositionProblem {
+    MissingKing(PieceColor),
+    ExtraKings(PieceColor, usize),
+    TooManyPieces(PieceColor, usize),
+    TooManyPawns(PieceColor, usize),
+    // More pieces of a kind than the pawns that are missing could have been promoted to
+    TooManyPromotions(PieceColor),
+    PawnOnBackRank(Square),
+    // The side that has just moved is still in check
+    OpponentInCheck(PieceColor),
+    CastlingWithoutPieces(PieceColor, CastleSide),
+    InvalidEnPassant(Square),
+}
+
+impl Display for PositionProblem {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            PositionProblem::MissingKing(color) => write!(f, "{} has no king", color.name()),
+            PositionProblem::ExtraKings(color, count) => {
+                write!(f, "{} has {} kings instead of one", color.name(), count)
+            }
+            PositionProblem::TooManyPieces(color, count) => {
+                write!(f, "{} has {} pieces, more than 16", color.name(), count)
+            }
+            PositionProblem::TooManyPawns(color, count) => {
+                write!(f, "{} has {} pawns, more than 8", color.name(), count)
+            }
+            PositionProblem::TooManyPromotions(color) => write!(
+                f,
+                "{} has more promoted pieces than missing pawns",
+                color.name()
+            ),
+            PositionProblem::PawnOnBackRank(square) => {
+                write!(
+                    f,
+                    "Pawn on {}, pawns cannot stand on the first or last rank",
+                    square
+                )
+            }
+            PositionProblem::OpponentInCheck(color) => write!(
+                f,
+                "{} is in check although it is not their turn",
+                color.name()
+            ),
+            PositionProblem::CastlingWithoutPieces(color, side) => write!(
+                f,
+                "{} cannot castle {} without its king and rook in place",
+                color.name(),
+                side.name()
+            ),
+            PositionProblem::InvalidEnPassant(square) => write!(
+                f,
+                "En passant square {} does not follow a double pawn step",
+                square
+            ),
+        }
+    }
+}
+
+/// Lists the problems of a position in one message
+pub fn describe_problems(problems: &[PositionProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Board {
+    /// Checks that the position is one that can be played on, listing every problem found
+    pub fn validate(&self) -> Result<(), Vec<PositionProblem>> {
+        let mut problems = Vec::new();
+
+        for color in [PieceColor::White, PieceColor::Black] {
+            match self.pieces(PieceKind::King, color).count() {
+                0 => problems.push(PositionProblem::MissingKing(color)),
+                1 => {}
+                count => problems.push(PositionProblem::ExtraKings(color, count)),
+            }
+
+            let count = self.pieces_of(color).count();
+            if count > 16 {
+                problems.push(PositionProblem::TooManyPieces(color, count));
+            }
+            let pawns = self.pieces(PieceKind::Pawn, color).count();
+            if pawns > 8 {
+                problems.push(PositionProblem::TooManyPawns(color, pawns));
+            }
+
+            let promoted = [
+                (PieceKind::Queen, 1),
+                (PieceKind::Rook, 2),
+                (PieceKind::Bishop, 2),
+                (PieceKind::Knight, 2),
+            ]
+            .iter()
+            .map(|&(kind, initial)| self.pieces(kind, color).count().saturating_sub(initial))
+            .sum::<usize>();
+            if pawns <= 8 && promoted > 8 - pawns {
+                problems.push(PositionProblem::TooManyPromotions(color));
+            }
+
+            let rank = color.back_rank();
+            for side in CastleSide::ALL {
+                let Some(file) = self.castling.rook_file(color, side) else {
+                    continue;
+                };
+                // Outside Chess960 the king starts on the e-file and the rooks in the corners
+                let is_standard = self.variant == Variant::Standard;
+                let king = self
+                    .king(color)
+                    .filter(|king| king.rank() == rank && (!is_standard || king.file() == 4));
+                let rook = self.get(Square::new(file, rank).unwrap());
+                let is_in_place = king.is_some_and(|king| match side {
+                    CastleSide::King => file > king.file(),
+                    CastleSide::Queen => file < king.file(),
+                }) && (!is_standard || file == side.rook_file())
+                    && rook.is_friend(color)
+                    && rook.is_kind(PieceKind::Rook);
+                if !is_in_place {
+                    problems.push(PositionProblem::CastlingWithoutPieces(color, side));
+                }
+            }
+        }
+
+        for square in self.pieces[PieceKind::Pawn as usize] {
+            if square.rank() == 0 || square.rank() == 7 {
+                problems.push(PositionProblem::PawnOnBackRank(square));
+            }
+        }
+
+        let color = self.which_color();
+        let opponent = color.opposite();
+        if self
+            .king(opponent)
+            .is_some_and(|king| self.is_attacked(king, color))
+        {
+            problems.push(PositionProblem::OpponentInCheck(opponent));
+        }
+
+        if let Some(target) = self.en_passant {
+            // The pawn that just moved two squares stands in front of the target, seen from the side to move
+            let (rank, direction) = match color {
+                PieceColor::White => (5, -1),
+                PieceColor::Black => (2, 1),
+            };
+            let pawn = target.offset(0, direction).map(|pawn| self.get(pawn));
+            let origin = target.offset(0, -direction).map(|origin| self.get(origin));
+            let is_double_step = target.rank() == rank
+                && pawn
+                    .is_some_and(|pawn| pawn.is_kind(PieceKind::Pawn) && pawn.is_friend(opponent))
+                && origin.is_some_and(|origin| origin.is_empty())
+                && self.get(target).is_empty();
+            if !is_double_step {
+                problems.push(PositionProblem::InvalidEnPassant(target));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
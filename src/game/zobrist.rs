@@ -0,0 +1,84 @@
+use super::*;
+
+struct Keys {
+    pieces: [[[u64; 64]; 7]; 2],
+    black: u64,
+    castling: [[u64; 2]; 2],
+    en_passant: [u64; 8],
+}
+
+lazy_static::lazy_static! {
+    static ref KEYS: Keys = {
+        // A fixed seed keeps the hashes identical between runs and between players
+        let mut state = 0x5445_524d_4943_4845_u64;
+        let mut next = move || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        let mut keys = Keys {
+            pieces: [[[0; 64]; 7]; 2],
+            black: 0,
+            castling: [[0; 2]; 2],
+            en_passant: [0; 8],
+        };
+        keys.pieces
+            .iter_mut()
+            .flatten()
+            .flatten()
+            .for_each(|key| *key = next());
+        keys.black = next();
+        keys.castling.iter_mut().flatten().for_each(|key| *key = next());
+        keys.en_passant.iter_mut().for_each(|key| *key = next());
+        keys
+    };
+}
+
+impl Board {
+    /// The Zobrist hash of the position, covering the pieces, the side to move, castling and en passant
+    pub fn hash(&self) -> u64 {
+        let mut hash = 0;
+
+        for color in [PieceColor::White, PieceColor::Black] {
+            for square in self.pieces_of(color) {
+                hash ^= KEYS.pieces[color as usize][self.get(square).kind as usize][square.index()];
+            }
+
+            for side in CastleSide::ALL {
+                if self.castling.has(color, side) {
+                    hash ^= KEYS.castling[color as usize][side as usize];
+                }
+            }
+        }
+
+        if self.which_color() == PieceColor::Black {
+            hash ^= KEYS.black;
+        }
+
+        // The en passant square only makes a difference when it can be taken
+        if let Some(target) = self.possible_en_passant() {
+            let color = self.which_color();
+            if !(Bitboard::pawn_attacks(target, color.opposite())
+                & self.pieces(PieceKind::Pawn, color))
+            .is_empty()
+            {
+                hash ^= KEYS.en_passant[target.file()];
+            }
+        }
+
+        hash
+    }
+
+    /// How many times the current position has appeared in the game
+    pub fn repetitions(&self) -> usize {
+        // Positions before the last capture or pawn move can never come back
+        let start = self.history.len().saturating_sub(self.halfmove + 1);
+        match self.history.last() {
+            Some(&hash) => self.history[start..].iter().filter(|&&h| h == hash).count(),
+            None => 1,
+        }
+    }
+}
@@ -1,8 +1,12 @@
+pub mod engine;
 pub mod game;
 pub mod local;
 pub mod prelude;
 pub mod remote;
+pub mod replay;
+pub mod uci;
 pub mod util;
+pub mod xboard;
 
 lazy_static::lazy_static! {
     pub static ref CONFIG: util::Config = util::get_config().unwrap().modify();
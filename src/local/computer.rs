@@ -0,0 +1,236 @@
+use super::*;
+
+/// A difficulty level of the built-in engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub limits: engine::SearchLimits,
+}
+
+pub const LEVELS: [Level; 5] = [
+    Level {
+        name: "Beginner",
+        description: "1 move ahead",
+        limits: engine::SearchLimits {
+            depth: Some(1),
+            movetime: None,
+            nodes: None,
+        },
+    },
+    Level {
+        name: "Easy",
+        description: "2 moves ahead",
+        limits: engine::SearchLimits {
+            depth: Some(2),
+            movetime: None,
+            nodes: None,
+        },
+    },
+    Level {
+        name: "Medium",
+        description: "3 moves ahead",
+        limits: engine::SearchLimits {
+            depth: Some(3),
+            movetime: None,
+            nodes: None,
+        },
+    },
+    Level {
+        name: "Hard",
+        description: "1 second per move",
+        limits: engine::SearchLimits {
+            depth: None,
+            movetime: Some(Duration::from_secs(1)),
+            nodes: None,
+        },
+    },
+    Level {
+        name: "Expert",
+        description: "5 seconds per move",
+        limits: engine::SearchLimits {
+            depth: None,
+            movetime: Some(Duration::from_secs(5)),
+            nodes: None,
+        },
+    },
+];
+
+/// What does the thinking for the computer
+pub enum Brain {
+    BuiltIn(Level),
+    External(uci::UciEngine),
+}
+
+/// The computer playing one side of a local game, with the built-in engine or an external one
+pub struct Computer {
+    pub color: game::PieceColor,
+    pub brain: Brain,
+}
+
+impl Computer {
+    /// The name written in saved games
+    pub fn name(&self) -> String {
+        match &self.brain {
+            Brain::BuiltIn(level) => format!("TermiChess {}", level.name),
+            Brain::External(engine) => engine.name.clone(),
+        }
+    }
+
+    /// Plays a move, or answers the draw offer made on its turn
+    pub fn respond(&mut self, board: &mut game::Board) {
+        if board.draw_offer {
+            // Only a worse position is worth a draw
+            if engine::evaluate_for(board, self.color) < 0 {
+                println!("Computer accepted the draw!");
+                board.draw();
+            } else {
+                println!("Computer declined the draw!");
+                board.decline_draw();
+            }
+            return;
+        }
+
+        let bar = indicatif::ProgressBar::new_spinner().with_message("Computer is thinking...");
+        bar.enable_steady_tick(Duration::from_millis(300));
+        let best = match &mut self.brain {
+            Brain::BuiltIn(level) => {
+                Ok(engine::best_move(board, within_clock(board, level.limits)))
+            }
+            Brain::External(engine) => engine
+                .best_move(
+                    board,
+                    within_clock(
+                        board,
+                        engine::SearchLimits::movetime(Duration::from_millis(
+                            CONFIG.engine_movetime,
+                        )),
+                    ),
+                )
+                .map(Some),
+        };
+        bar.finish_and_clear();
+
+        if board.check_flag() {
+            println!("{}", style("Computer ran out of time!").red());
+            return;
+        }
+
+        match best {
+            Ok(Some(mv)) => {
+                println!("Computer: {}", style(board.to_san(mv)).bold());
+                *board = board.perform(mv).unwrap();
+            }
+            // The search only comes back empty when the game is already over
+            Ok(None) => {}
+            Err(err) => {
+                println!("{}: {}", style("Error").red(), err);
+                board.abandon(self.color);
+            }
+        }
+    }
+}
+
+/// Keeps the computer from thinking for longer than its clock allows
+fn within_clock(board: &game::Board, limits: engine::SearchLimits) -> engine::SearchLimits {
+    let Some(clock) = &board.clock else {
+        return limits;
+    };
+    let left = clock.left(
+        board.which_color() == game::PieceColor::White,
+        std::time::Instant::now(),
+    );
+    let Some(budget) = engine::SearchLimits::clock(left, Duration::ZERO, None).movetime else {
+        return limits;
+    };
+    engine::SearchLimits {
+        movetime: Some(
+            limits
+                .movetime
+                .map_or(budget, |movetime| movetime.min(budget)),
+        ),
+        ..limits
+    }
+}
+
+fn choose_color() -> game::PieceColor {
+    let color = dialoguer::Select::new()
+        .with_prompt("Play as")
+        .items(&["White", "Black", "Random"])
+        .default(0)
+        .interact()
+        .unwrap();
+    match color {
+        0 => game::PieceColor::White,
+        1 => game::PieceColor::Black,
+        _ => {
+            if rand::random() {
+                game::PieceColor::White
+            } else {
+                game::PieceColor::Black
+            }
+        }
+    }
+}
+
+pub fn play_against_computer() {
+    let color = choose_color();
+    let level = dialoguer::Select::new()
+        .with_prompt("Difficulty")
+        .items(&LEVELS.map(|level| format!("{}({})", level.name, level.description)))
+        .default(2)
+        .interact()
+        .unwrap();
+
+    let mut board = util::choose_variant("New Game");
+    if let Some(control) = util::choose_time_control("Time control") {
+        board.set_time_control(control);
+    }
+    println!("You are {}!", style(color.name()).bold());
+    play_locally_with(
+        board,
+        Some(Computer {
+            color: color.opposite(),
+            brain: Brain::BuiltIn(LEVELS[level]),
+        }),
+    );
+}
+
+/// Plays against the external UCI engine set in the configuration
+pub fn play_against_engine() {
+    let error = style("Error").red();
+    if CONFIG.engine_path.is_empty() {
+        println!(
+            "{}: Set `engine_path` in termichess.toml to the engine to play against",
+            error
+        );
+        return;
+    }
+
+    let mut engine = match uci::UciEngine::spawn(&CONFIG.engine_path) {
+        Ok(engine) => engine,
+        Err(err) => {
+            println!("{}: {}", error, err);
+            return;
+        }
+    };
+    if let Err(err) = engine.new_game() {
+        println!("{}: {}", error, err);
+        return;
+    }
+    println!("Loaded {}!", style(&engine.name).bold());
+
+    let color = choose_color();
+    let mut board = util::choose_variant("New Game");
+    if let Some(control) = util::choose_time_control("Time control") {
+        board.set_time_control(control);
+    }
+    println!("You are {}!", style(color.name()).bold());
+    play_locally_with(
+        board,
+        Some(Computer {
+            color: color.opposite(),
+            brain: Brain::External(engine),
+        }),
+    );
+}
@@ -1,4 +1,8 @@
+mod computer;
 mod play;
+mod takeback;
 
 pub(super) use crate::prelude::*;
+pub use computer::*;
 pub use play::*;
+pub use takeback::*;
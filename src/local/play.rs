@@ -1,11 +1,81 @@
 use super::*;
 
 pub fn play_locally() {
-    let mut board = game::Board::new();
+    let start = dialoguer::Select::new()
+        .with_prompt("New Game")
+        .items(&["Standard", "Chess960", "From FEN"])
+        .default(0)
+        .interact()
+        .unwrap();
+    match start {
+        0 => play_locally_on_clock(game::Board::new()),
+        1 => play_locally_on_clock(util::choose_chess960()),
+        _ => {
+            let fen = dialoguer::Input::<String>::new()
+                .with_prompt("Starting position in FEN(leave empty for a new game)")
+                .allow_empty(true)
+                .interact()
+                .unwrap();
+            play_locally_from_fen(&fen);
+        }
+    }
+}
+
+pub fn play_locally_from_fen(fen: &str) {
+    if fen.trim().is_empty() {
+        play_locally_on_clock(game::Board::new());
+    } else {
+        match game::Board::from_fen(fen) {
+            Ok(board) => play_locally_on_clock(board),
+            Err(err) => println!("{}: {}", style("Error").red(), err),
+        }
+    }
+}
+
+pub fn play_locally_from(board: game::Board) {
+    play_locally_with(board, None);
+}
+
+fn play_locally_on_clock(mut board: game::Board) {
+    if let Some(control) = util::choose_time_control("Time control") {
+        board.set_time_control(control);
+    }
+    play_locally_from(board);
+}
+
+/// Asks the player of `color` whether their opponent may take back the last move
+fn accept_takeback(color: game::PieceColor) -> bool {
+    dialoguer::Confirm::new()
+        .with_prompt(format!("{}: takeback requested, accept?", color.name()))
+        .interact()
+        .unwrap()
+}
+
+/// Plays a local game, with the computer taking one side if given
+pub fn play_locally_with(mut board: game::Board, mut computer: Option<Computer>) {
     let mut err = None;
     let error = style("Error").red();
+    let mut takebacks = Takebacks::new();
 
     while board.status == game::Status::Playing {
+        // Presses the clock for whoever moved last, their flag may have fallen meanwhile
+        board.sync_clock();
+        if board.status != game::Status::Playing {
+            break;
+        }
+
+        if let Some(computer) = computer
+            .as_mut()
+            .filter(|computer| computer.color == board.which_color())
+        {
+            let before = board.clone();
+            computer.respond(&mut board);
+            if board.moves.len() > before.moves.len() {
+                takebacks.record(before);
+            }
+            continue;
+        }
+
         println!("{}", board);
         if let Some(msg) = err {
             println!("{}: {}", error, msg);
@@ -18,6 +88,11 @@ pub fn play_locally() {
             .unwrap();
         let command = util::parse_raw(str);
 
+        if board.check_flag() {
+            println!("{}", style("Time is up!").red());
+            continue;
+        }
+
         if board.draw_offer && command != util::Command::Draw {
             println!("Draw offer declined!");
             board.decline_draw();
@@ -26,16 +101,19 @@ pub fn play_locally() {
 
         match command {
             util::Command::Chess(str) => {
-                let notation = board.translate(&str);
-                if let Ok(notation) = notation {
-                    let next = board.perform(notation);
-                    if next.is_none() {
-                        err = Some("Invalid move! This leads to a check!".to_string());
-                    } else {
-                        board = next.unwrap();
+                let played = board
+                    .translate(&str)
+                    .and_then(|mv| Ok((mv, board.perform(mv)?)));
+                match played {
+                    Ok((mv, next)) => {
+                        println!(
+                            "{}: {}",
+                            board.which_color().name(),
+                            style(board.to_san(mv)).bold()
+                        );
+                        takebacks.record(std::mem::replace(&mut board, next));
                     }
-                } else {
-                    err = Some(format!("{}", notation.unwrap_err()));
+                    Err(e) => err = Some(e.to_string()),
                 }
             }
             util::Command::Resign => {
@@ -46,14 +124,65 @@ pub fn play_locally() {
                 println!("You offered a draw!");
                 board.draw();
             }
+            util::Command::Claim => {
+                if board.claim_draw() {
+                    println!("You claimed a draw!");
+                } else {
+                    err = Some("There is no draw to claim!".to_string());
+                }
+            }
+            util::Command::Fen => {
+                println!("{}", board.to_fen());
+            }
             util::Command::Chat(_str) => {
                 println!(
                     "{}",
                     style("Why use chat in local games? Just talk to them in person!").bold()
                 );
             }
+            util::Command::Timeout => unreachable!("only the server calls a timeout"),
+            util::Command::Undo => {
+                // Against the computer its reply is taken back too, so that it is your turn again
+                let plies = match &computer {
+                    Some(computer) if takebacks.last_mover() == Some(computer.color) => 2,
+                    _ => 1,
+                };
+                if !takebacks.can_undo(plies) {
+                    err = Some("There is no move to take back!".to_string());
+                } else if computer.is_some() || accept_takeback(board.which_color()) {
+                    takebacks.undo(&mut board, plies);
+                    println!("Move taken back!");
+                } else {
+                    println!("Takeback declined!");
+                }
+            }
+            util::Command::Redo => {
+                let plies = if computer.is_some() && takebacks.can_redo(2) {
+                    2
+                } else {
+                    1
+                };
+                if takebacks.redo(&mut board, plies) {
+                    println!("Move played again!");
+                } else {
+                    err = Some("There is no move to play again!".to_string());
+                }
+            }
         }
         println!();
     }
+    board.sync_clock();
     println!("{}", board);
+
+    let info = match computer {
+        Some(computer) => {
+            let name = computer.name();
+            match computer.color {
+                game::PieceColor::White => game::GameInfo::new("Computer Game", &name, "You"),
+                game::PieceColor::Black => game::GameInfo::new("Computer Game", "You", &name),
+            }
+        }
+        None => game::GameInfo::new("Local Game", "White", "Black"),
+    };
+    util::offer_pgn_save(&board, &info);
 }
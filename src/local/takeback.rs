@@ -0,0 +1,74 @@
+use super::*;
+
+/// The positions before each move of a local game, and those taken back, for `undo` and `redo`
+#[derive(Debug, Clone, Default)]
+pub struct Takebacks {
+    undo: Vec<game::Board>,
+    redo: Vec<game::Board>,
+}
+
+impl Takebacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers the position before a move, the moves taken back cannot be redone anymore
+    pub fn record(&mut self, before: game::Board) {
+        self.undo.push(before);
+        self.redo.clear();
+    }
+
+    /// Who made the last move that can be taken back
+    pub fn last_mover(&self) -> Option<game::PieceColor> {
+        self.undo.last().map(game::Board::which_color)
+    }
+
+    pub fn can_undo(&self, plies: usize) -> bool {
+        plies > 0 && self.undo.len() >= plies
+    }
+
+    pub fn can_redo(&self, plies: usize) -> bool {
+        plies > 0 && self.redo.len() >= plies
+    }
+
+    /// Takes back the last `plies` moves, if there are that many
+    pub fn undo(&mut self, board: &mut game::Board, plies: usize) -> bool {
+        if !self.can_undo(plies) {
+            return false;
+        }
+        travel(board, &mut self.undo, &mut self.redo, plies);
+        true
+    }
+
+    /// Plays again the last `plies` moves taken back, if there are that many
+    pub fn redo(&mut self, board: &mut game::Board, plies: usize) -> bool {
+        if !self.can_redo(plies) {
+            return false;
+        }
+        travel(board, &mut self.redo, &mut self.undo, plies);
+        true
+    }
+}
+
+/// Moves from one stack of positions to the other, keeping the clocks as they are:
+/// the time used is not given back, only the side the clock runs for changes
+fn travel(
+    board: &mut game::Board,
+    from: &mut Vec<game::Board>,
+    to: &mut Vec<game::Board>,
+    plies: usize,
+) {
+    for _ in 0..plies {
+        let mut position = from.pop().unwrap();
+        position.clock = board.clock.take();
+        to.push(std::mem::replace(board, position));
+    }
+    let white = board.which_color() == game::PieceColor::White;
+    if let Some(clock) = board
+        .clock
+        .as_mut()
+        .filter(|clock| clock.running().is_some())
+    {
+        clock.start(white, std::time::Instant::now());
+    }
+}
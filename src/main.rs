@@ -4,25 +4,58 @@ use ::termichess::prelude::*;
 async fn main() {
     lazy_static::initialize(&CONFIG);
 
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("perft") => {
+            perft(&args[1..]);
+            return;
+        }
+        Some("--uci") => {
+            uci::run_uci();
+            return;
+        }
+        Some("--xboard") => {
+            xboard::run_xboard();
+            return;
+        }
+        Some("local") => {
+            local::play_locally_from_fen(&args[1..].join(" "));
+            return;
+        }
+        _ => {}
+    }
+
     loop {
         let mode = dialoguer::Select::new()
             .with_prompt("Main Menu")
-            .items(&["Play Online", "Play Local", "Information", "Quit"])
+            .items(&[
+                "Play Online",
+                "Play Local",
+                "Play vs Computer",
+                "Play vs Engine",
+                "Replay Game",
+                "Information",
+                "Quit",
+            ])
             .default(0)
             .interact()
             .unwrap();
         match mode {
             0 => remote::play_remotely().await,
             1 => local::play_locally(),
-            2 => {
+            2 => local::play_against_computer(),
+            3 => local::play_against_engine(),
+            4 => replay::replay_game(),
+            5 => {
                 println!(
                     "Welcome to {}! This is a simple chess game written in {}. Licensed under {}.",
                     console::style("TermiChess").bold().magenta(),
                     console::style("Rust").bold().cyan(),
                     console::style("MIT License").bold().cyan()
                 );
-                println!("Currently, you can play {} against another player, or play {} using a personal server.\n",
+                println!("Currently, you can play {} against another player or the {}, or play {} using a personal server.\n",
                     console::style("locally").bold().green(),
+                    console::style("computer").bold().green(),
                     console::style("online").bold().green()
                 );
                 println!(
@@ -31,7 +64,7 @@ async fn main() {
                 );
                 println!()
             }
-            3 => {
+            6 => {
                 println!("Goodbye!");
                 break;
             }
@@ -39,3 +72,43 @@ async fn main() {
         }
     }
 }
+
+fn perft(args: &[String]) {
+    let error = console::style("Error").red().bold();
+
+    if args.len() < 2 {
+        println!("Usage: termichess perft <fen|startpos> <depth>");
+        return;
+    }
+    // The FEN may come unquoted, split over several arguments
+    let fen = args[..args.len() - 1].join(" ");
+    let depth = &args[args.len() - 1];
+    let board = if fen == "startpos" {
+        game::Board::new()
+    } else {
+        match game::Board::from_fen(&fen) {
+            Ok(board) => board,
+            Err(err) => {
+                println!("{}: {}", error, err);
+                return;
+            }
+        }
+    };
+    let Ok(depth) = depth.parse::<usize>() else {
+        println!("{}: Invalid depth \"{}\"", error, depth);
+        return;
+    };
+
+    let start = std::time::Instant::now();
+    let divide = board.divide(depth);
+    for (mv, nodes) in &divide {
+        println!("{}: {}", mv, nodes);
+    }
+    let total: u64 = match depth {
+        0 => 1,
+        _ => divide.iter().map(|(_, nodes)| nodes).sum(),
+    };
+    println!();
+    println!("Nodes searched: {}", total);
+    println!("Time: {:.3}s", start.elapsed().as_secs_f64());
+}
@@ -3,14 +3,16 @@ pub(crate) use reqwest::StatusCode;
 pub(crate) use serde::{Deserialize, Serialize};
 pub(crate) use serde_json::json;
 pub(crate) use std::cell::Cell;
-pub(crate) use std::collections::HashSet;
 pub(crate) use std::fmt::{self, Display, Formatter};
 pub(crate) use std::hash::{Hash, Hasher};
-pub(crate) use std::sync::{Arc, RwLock};
 pub(crate) use std::time::Duration;
 
+pub use crate::engine;
 pub use crate::game;
 pub use crate::local;
 pub use crate::remote;
+pub use crate::replay;
+pub use crate::uci;
 pub use crate::util;
+pub use crate::xboard;
 pub use crate::CONFIG;
@@ -16,22 +16,44 @@ struct Connection {
 }
 
 impl Connection {
-    async fn login(mut self) -> Result<Self, String> {
+    /// The color this player is playing
+    fn color(&self) -> game::PieceColor {
+        if self.player {
+            game::PieceColor::White
+        } else {
+            game::PieceColor::Black
+        }
+    }
+
+    /// Joins the room, creating it with `board` and `time_control` if it does not exist,
+    /// and returns the board of the room
+    async fn login(
+        mut self,
+        board: &game::Board,
+        time_control: Option<game::TimeControl>,
+    ) -> Result<(Self, game::Board), String> {
         let bar = indicatif::ProgressBar::new_spinner().with_message("Connecting to the server...");
         bar.enable_steady_tick(Duration::from_millis(300));
 
         let client = reqwest::Client::new();
         let res = client
             .post(format!("{}/chess/login", self.address))
-            .body(json!(server::LoginRequest { room: self.room }).to_string())
+            .body(
+                json!(server::LoginRequest {
+                    room: self.room,
+                    board: Some(board.serialize()),
+                    time_control: time_control.map(|control| control.to_string())
+                })
+                .to_string(),
+            )
             .send()
             .await
             .map_err(|e| e.to_string())?;
 
         bar.finish_with_message(format!("Connected to {}!", self.address));
 
-        match serde_json::from_str(&res.text().await.map_err(|e| e.to_string())?).map(
-            |res: server::LoginResponse| {
+        match serde_json::from_str(&res.text().await.map_err(|e| e.to_string())?).inspect(
+            |res: &server::LoginResponse| {
                 if res.player {
                     println!(
                         "{} You are {}!",
@@ -45,18 +67,32 @@ impl Connection {
                         style("Black").on_black().white()
                     );
                 }
-                res
             },
         ) {
             Ok(res) => {
                 self.player = res.player;
-                Ok(self)
+                // Servers from older releases do not send the board, their games are always standard
+                let mut board = match res.board {
+                    Some(board) => game::Board::deserialize(board)
+                        .map_err(|e| format!("The server sent an invalid board: {}", e))?,
+                    None => game::Board::new(),
+                };
+                if board.variant != game::Variant::Standard {
+                    println!("This game is {}!", style(board.variant.name()).bold());
+                }
+                if let Some(control) = res.time_control.and_then(|control| control.parse().ok()) {
+                    println!("Time control: {}", style(&control).bold());
+                    board.set_time_control(control);
+                }
+                Ok((self, board))
             }
             Err(_e) => Err("Failed to join the game".to_string()),
         }
     }
 
-    async fn play(&self, cmd: String, board: &game::Board) -> Result<(), String> {
+    /// Sends a command made on `board`, taking over the clocks of the server, which flags
+    /// the player instead if their time had already run out
+    async fn play(&self, cmd: String, board: &mut game::Board) -> Result<(), String> {
         let client = reqwest::Client::new();
         let res = client
             .post(format!("{}/chess/play", self.address))
@@ -65,7 +101,8 @@ impl Connection {
                     room: self.room,
                     player: self.player,
                     cmd,
-                    board: board.serialize()
+                    board: game::Board::serialize(board),
+                    termination: board.termination.map(|termination| termination.serialize())
                 })
                 .to_string(),
             )
@@ -73,7 +110,17 @@ impl Connection {
             .await
             .map_err(|e| e.to_string())?;
         match res.status() {
-            StatusCode::OK => Ok(()),
+            StatusCode::OK => {
+                // Servers from older releases answer with an empty object, which has no clock
+                let response: server::PlayResponse =
+                    serde_json::from_str(&res.text().await.map_err(|e| e.to_string())?)
+                        .map_err(|_e| "Failed to play the move".to_string())?;
+                sync_clock(board, response.clock);
+                if response.flagged {
+                    board.flag(self.color());
+                }
+                Ok(())
+            }
             _ => Err(res.text().await.map_err(|e| e.to_string())?),
         }
     }
@@ -99,7 +146,11 @@ impl Connection {
                     Err(_e) => Err("Failed to query the game".to_string()),
                 }
             }
-            StatusCode::NOT_FOUND => Err(res.text().await.map_err(|e| e.to_string())?),
+            StatusCode::NOT_FOUND => {
+                // The room is gone, the opponent has logged out
+                self.status.set(ConnectionStatus::Closed);
+                Err(res.text().await.map_err(|e| e.to_string())?)
+            }
             _ => Ok(None),
         }
     }
@@ -140,7 +191,7 @@ impl Connection {
         }
     }
 
-    async fn log_back(&self) -> Result<String, ()> {
+    async fn log_back(&self) -> Result<server::LogBackResponse, ()> {
         let client = reqwest::Client::new();
         let res = client
             .post(format!("{}/chess/log_back", self.address))
@@ -161,7 +212,7 @@ impl Connection {
                         Ok(res) => res,
                         Err(_e) => return Err(()),
                     };
-                Ok(response.board)
+                Ok(response)
             }
             _ => Err(()),
         }
@@ -190,6 +241,13 @@ impl Connection {
     }
 }
 
+/// Takes over the time left on the server's clocks
+fn sync_clock(board: &mut game::Board, state: Option<game::ClockState>) {
+    if let (Some(clock), Some(state)) = (board.clock.as_mut(), state) {
+        clock.set_state(&state, std::time::Instant::now());
+    }
+}
+
 async fn play_remotely_with(connection: Connection, mut board: game::Board) -> Connection {
     let error = style("Error").red().bold();
     let terminate = style("Terminating due to error").red().bold();
@@ -217,6 +275,11 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
 
         bar.finish_with_message("Opponent joined!".to_string());
 
+        // The server starts White's clock as the opponent joins, and keeps it from then on
+        if let Some(clock) = board.clock.as_mut() {
+            clock.start(true, std::time::Instant::now());
+        }
+
         connection.status.set(ConnectionStatus::Open);
     }
 
@@ -254,7 +317,13 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
 
                 let q = connection.query().await;
                 if let Err(err) = q {
-                    println!("{} {}", terminate, err);
+                    bar.finish_and_clear();
+                    if connection.status.get() == ConnectionStatus::Closed {
+                        println!("Opponent has left the game!");
+                        board.abandon(connection.color().opposite());
+                    } else {
+                        println!("{} {}", terminate, err);
+                    }
                     break 'game_loop;
                 }
                 query = q.unwrap();
@@ -267,12 +336,16 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
             }
             let query = query.unwrap();
             bar.finish_and_clear();
-            if !query.cmd.starts_with("chat") {
+            sync_clock(&mut board, query.clock);
+            let command = util::parse_remote(query.cmd.clone());
+            // Moves are announced in SAN once they are known to be legal
+            if !matches!(
+                command,
+                util::Command::Chess(_) | util::Command::Chat(_) | util::Command::Timeout
+            ) {
                 println!("Opponent: {}", query.cmd);
             }
 
-            let command = util::parse_raw(query.cmd);
-
             (command, "Opponent", None)
         };
 
@@ -280,7 +353,7 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
             println!("Draw offer has been declined!");
 
             if let Some(str) = player_str {
-                let play = connection.play(str, &board).await;
+                let play = connection.play(str, &mut board).await;
                 if let Err(err) = play {
                     println!("{} {}", terminate, err);
                     break 'game_loop;
@@ -293,26 +366,32 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
         } else {
             match command {
                 util::Command::Chess(str) => {
-                    let notation = board.translate(&str);
-                    if let Ok(notation) = notation {
-                        let next = board.perform(notation);
-                        if next.is_none() {
-                            err = Some("Invalid move! This leads to a check!".to_string());
-                        } else {
+                    let played = board
+                        .translate(&str)
+                        .and_then(|mv| Ok((mv, board.perform(mv)?)));
+                    match played {
+                        Ok((mv, mut next)) => {
                             is_turn = !is_turn;
 
-                            board = next.unwrap();
-
                             if let Some(str) = player_str {
-                                let play = connection.play(str, &board).await;
+                                let play = connection.play(str, &mut next).await;
                                 if let Err(err) = play {
                                     println!("{} {}", terminate, err);
                                     break 'game_loop;
                                 }
                             }
+
+                            // The server turns down a move made after the flag has fallen
+                            if next.termination == Some(game::Termination::Timeout) {
+                                println!("{}", style("You ran out of time!").red());
+                                board.clock = next.clock;
+                                board.flag(connection.color());
+                            } else {
+                                println!("{}: {}", pronoun, style(board.to_san(mv)).bold());
+                                board = next;
+                            }
                         }
-                    } else {
-                        err = Some(format!("{}", notation.unwrap_err()));
+                        Err(e) => err = Some(e.to_string()),
                     }
                 }
                 util::Command::Resign => {
@@ -324,7 +403,7 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
                     board.resign();
 
                     if let Some(str) = player_str {
-                        let play = connection.play(str, &board).await;
+                        let play = connection.play(str, &mut board).await;
                         if let Err(err) = play {
                             println!("{} {}", terminate, err);
                             break 'game_loop;
@@ -337,16 +416,43 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
                     board.draw();
 
                     if let Some(str) = player_str {
-                        let play = connection.play(str, &board).await;
+                        let play = connection.play(str, &mut board).await;
                         if let Err(err) = play {
                             println!("{} {}", terminate, err);
                             break 'game_loop;
                         }
                     }
                 }
+                util::Command::Claim => {
+                    if board.claim_draw() {
+                        println!("{} claimed a draw!", pronoun);
+
+                        is_turn = !is_turn;
+
+                        if let Some(str) = player_str {
+                            let play = connection.play(str, &mut board).await;
+                            if let Err(err) = play {
+                                println!("{} {}", terminate, err);
+                                break 'game_loop;
+                            }
+                        }
+                    } else {
+                        err = Some("There is no draw to claim!".to_string());
+                    }
+                }
+                util::Command::Fen => {
+                    println!("{}", board.to_fen());
+                }
+                util::Command::Undo | util::Command::Redo => {
+                    err = Some("Takebacks are only available in local games!".to_string());
+                }
+                util::Command::Timeout => {
+                    println!("{}", style("Opponent ran out of time!").green());
+                    board.flag(connection.color().opposite());
+                }
                 util::Command::Chat(str) => {
                     if let Some(str) = player_str {
-                        let play = connection.play(str, &board).await;
+                        let play = connection.play(str, &mut board).await;
                         if let Err(err) = play {
                             println!("{} {}", terminate, err);
                             break 'game_loop;
@@ -378,6 +484,13 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
         }
 
         let _ = connection.logout().await;
+
+        let (white, black) = if connection.player {
+            ("You", "Opponent")
+        } else {
+            ("Opponent", "You")
+        };
+        util::offer_pgn_save(&board, &game::GameInfo::new("Online Game", white, black));
     }
 
     connection
@@ -385,36 +498,41 @@ async fn play_remotely_with(connection: Connection, mut board: game::Board) -> C
 
 async fn play_remotely_with_any(connection: Option<Connection>) -> Option<Connection> {
     let (connection, board) = match connection {
-        None => (
-            {
-                let connection = Connection::build().login().await;
-                if let Err(err) = connection {
+        None => {
+            let connection = Connection::build();
+            let board = util::choose_variant("Variant(if you create the room)");
+            let time_control = util::choose_time_control("Time control(if you create the room)");
+            match connection.login(&board, time_control).await {
+                Ok(joined) => joined,
+                Err(err) => {
                     println!("{} {}", style("Error").red().bold(), err);
                     return None;
                 }
-                connection.unwrap()
-            },
-            game::Board::new(),
-        ),
+            }
+        }
         Some(connection) => match connection.status.get() {
             ConnectionStatus::Uninitialized => {
-                let connection = connection.login().await;
-                if let Err(err) = connection {
-                    println!("{} {}", style("Error").red().bold(), err);
-                    return None;
+                let board = util::choose_variant("Variant(if you create the room)");
+                let time_control =
+                    util::choose_time_control("Time control(if you create the room)");
+                match connection.login(&board, time_control).await {
+                    Ok(joined) => joined,
+                    Err(err) => {
+                        println!("{} {}", style("Error").red().bold(), err);
+                        return None;
+                    }
                 }
-                (connection.unwrap(), game::Board::new())
             }
             _ => {
                 let bar = indicatif::ProgressBar::new_spinner()
                     .with_message("Attempting to reconnect to the server...");
                 bar.enable_steady_tick(Duration::from_millis(300));
                 let start_time = std::time::Instant::now();
-                let board = loop {
+                let response = loop {
                     match connection.log_back().await {
-                        Ok(board) => {
+                        Ok(response) => {
                             bar.finish_with_message("Reconnected to the server!".to_string());
-                            break board;
+                            break response;
                         }
                         Err(_) => {
                             tokio::time::sleep(Duration::from_millis(300)).await;
@@ -426,7 +544,27 @@ async fn play_remotely_with_any(connection: Option<Connection>) -> Option<Connec
                         return Some(connection);
                     }
                 };
-                (connection, game::Board::deserialize(board).unwrap())
+                match game::Board::deserialize(response.board) {
+                    Ok(mut board) => {
+                        if let Some(control) = response
+                            .time_control
+                            .and_then(|control| control.parse().ok())
+                        {
+                            board.set_time_control(control);
+                            sync_clock(&mut board, response.clock);
+                        }
+                        (connection, board)
+                    }
+                    Err(err) => {
+                        println!(
+                            "{}: The server sent an invalid board: {}",
+                            style("Error").red().bold(),
+                            err
+                        );
+                        let _ = connection.logout().await;
+                        return Some(connection);
+                    }
+                }
             }
         },
     };
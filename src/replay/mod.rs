@@ -0,0 +1,4 @@
+mod view;
+
+pub(super) use crate::prelude::*;
+pub use view::*;
@@ -0,0 +1,128 @@
+use super::*;
+
+/// Loads a PGN file and steps through one of its games
+pub fn replay_game() {
+    let error = style("Error").red();
+
+    let path = dialoguer::Input::<String>::new()
+        .with_prompt("PGN file")
+        .interact()
+        .unwrap();
+    let text = match std::fs::read_to_string(path.trim()) {
+        Ok(text) => text,
+        Err(err) => {
+            println!("{}: Failed to read the file: {}", error, err);
+            return;
+        }
+    };
+    let games = match game::parse_pgn(&text) {
+        Ok(games) if !games.is_empty() => games,
+        Ok(_) => {
+            println!("{}: The file contains no games", error);
+            return;
+        }
+        Err(err) => {
+            println!("{}: {}", error, err);
+            return;
+        }
+    };
+
+    let index = if games.len() == 1 {
+        0
+    } else {
+        let items = games.iter().map(describe).collect::<Vec<_>>();
+        dialoguer::Select::new()
+            .with_prompt("Choose a game")
+            .items(&items)
+            .default(0)
+            .interact()
+            .unwrap()
+    };
+    let game = &games[index];
+
+    match game.replay() {
+        Ok(boards) => view(game, &boards),
+        Err(err) => println!("{}: {}", error, err),
+    }
+}
+
+fn describe(game: &game::PgnGame) -> String {
+    format!(
+        "{} vs {} ({}) {}",
+        game.tag("White").unwrap_or("?"),
+        game.tag("Black").unwrap_or("?"),
+        game.result,
+        game.tag("Event").unwrap_or_default()
+    )
+}
+
+fn view(game: &game::PgnGame, boards: &[game::Board]) {
+    let term = console::Term::stdout();
+    let mut ply = 0;
+
+    loop {
+        term.clear_screen().ok();
+        println!("{}", style(describe(game)).bold());
+        println!("{}", boards[ply]);
+
+        if ply == 0 {
+            println!("Start of the game");
+            for comment in &game.comments {
+                println!("{}", style(comment).cyan());
+            }
+        } else {
+            let board = &boards[ply - 1];
+            let mv = &game.moves[ply - 1];
+            // The move is written again in SAN, as files from elsewhere may use other notations
+            let san = boards[ply]
+                .moves
+                .last()
+                .map_or(mv.san.clone(), |&played| board.to_san(played));
+            let number = board.turn.div_ceil(2);
+            let dots = match board.which_color() {
+                game::PieceColor::White => ".",
+                game::PieceColor::Black => "...",
+            };
+            let nags = mv
+                .nags
+                .iter()
+                .map(|nag| format!(" ${}", nag))
+                .collect::<String>();
+            println!(
+                "Move {}/{}: {}{} {}{}",
+                ply,
+                game.moves.len(),
+                number,
+                dots,
+                style(san).bold(),
+                nags
+            );
+            for comment in &mv.comments {
+                println!("{}", style(comment).cyan());
+            }
+            if !mv.variations.is_empty() {
+                println!("{} variation(s) not shown", mv.variations.len());
+            }
+        }
+        if ply == game.moves.len() {
+            println!("Result: {}", style(&game.result).bold());
+        }
+        println!(
+            "{}",
+            style("[→/d] next  [←/a] back  [Home] start  [End] end  [q] quit").dim()
+        );
+
+        match term.read_key() {
+            Ok(console::Key::ArrowRight | console::Key::Enter | console::Key::Char('d' | ' ')) => {
+                ply = (ply + 1).min(game.moves.len())
+            }
+            Ok(console::Key::ArrowLeft | console::Key::Backspace | console::Key::Char('a')) => {
+                ply = ply.saturating_sub(1)
+            }
+            Ok(console::Key::Home) => ply = 0,
+            Ok(console::Key::End) => ply = game.moves.len(),
+            Ok(console::Key::Escape | console::Key::Char('q')) | Err(_) => break,
+            _ => {}
+        }
+    }
+}
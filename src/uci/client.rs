@@ -0,0 +1,190 @@
+use super::*;
+
+// How long an engine may take to start up or to answer `isready`
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+// How much longer than its move time an engine may take to answer `go` or `stop`
+const ANSWER_MARGIN: Duration = Duration::from_secs(2);
+
+message_error! {
+    pub struct EngineError;
+}
+
+/// An external engine speaking UCI, running as a child process
+pub struct UciEngine {
+    child: Child,
+    stdin: ChildStdin,
+    // Lines from the engine, read on another thread so that a silent engine can time out
+    lines: Receiver<String>,
+    pub name: String,
+    // Whether the engine has the UCI_Chess960 option
+    chess960: bool,
+}
+
+impl UciEngine {
+    /// Starts the engine at `path` and waits for it to be ready
+    pub fn spawn(path: &str) -> Result<Self, EngineError> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| err!(EngineError, "Failed to start the engine {}: {}", path, e))?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = UciEngine {
+            child,
+            stdin,
+            lines,
+            name: path.to_string(),
+            chess960: false,
+        };
+        engine.send("uci")?;
+        loop {
+            let line = engine.receive(HANDSHAKE_TIMEOUT)?;
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = name.trim().to_string();
+            } else if line.starts_with("option name UCI_Chess960 ") {
+                engine.chess960 = true;
+            } else if line.trim() == "uciok" {
+                break;
+            }
+        }
+        engine.wait_ready()?;
+
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), EngineError> {
+        writeln!(self.stdin, "{}", command)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| err!(EngineError, "Failed to talk to the engine: {}", e))
+    }
+
+    fn receive(&self, timeout: Duration) -> Result<String, EngineError> {
+        self.lines.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => err!(EngineError, "The engine is not responding"),
+            RecvTimeoutError::Disconnected => err!(EngineError, "The engine has exited"),
+        })
+    }
+
+    /// Waits for the `bestmove` that ends the search, up to the deadline
+    fn receive_best_move(&self, deadline: Instant) -> Result<String, EngineError> {
+        loop {
+            let line = self.receive(deadline.saturating_duration_since(Instant::now()))?;
+            if let Some(reply) = line.strip_prefix("bestmove") {
+                return Ok(reply
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string());
+            }
+        }
+    }
+
+    fn wait_ready(&mut self) -> Result<(), EngineError> {
+        self.send("isready")?;
+        while self.receive(HANDSHAKE_TIMEOUT)?.trim() != "readyok" {}
+        Ok(())
+    }
+
+    /// Tells the engine that the next position is from another game
+    pub fn new_game(&mut self) -> Result<(), EngineError> {
+        self.send("ucinewgame")?;
+        self.wait_ready()
+    }
+
+    /// Sends the whole game so far, from its starting position, and asks for the best move
+    pub fn best_move(
+        &mut self,
+        board: &game::Board,
+        limits: engine::SearchLimits,
+    ) -> Result<game::Move, EngineError> {
+        let chess960 = board.variant == game::Variant::Chess960;
+        if chess960 && !self.chess960 {
+            return Err(err!(EngineError, "The engine does not play Chess960"));
+        }
+        // Set for every game, so that a standard game after a Chess960 one turns it off again
+        if self.chess960 {
+            self.send(&format!("setoption name UCI_Chess960 value {}", chess960))?;
+        }
+
+        let mut position = match &board.setup {
+            Some(fen) => format!("position fen {}", fen),
+            None => "position startpos".to_string(),
+        };
+        if !board.moves.is_empty() {
+            position.push_str(" moves");
+            for mv in &board.moves {
+                position.push_str(&format!(" {}", mv));
+            }
+        }
+        self.send(&position)?;
+
+        let mut go = "go".to_string();
+        if let Some(depth) = limits.depth {
+            go.push_str(&format!(" depth {}", depth));
+        }
+        if let Some(nodes) = limits.nodes {
+            go.push_str(&format!(" nodes {}", nodes));
+        }
+        if let Some(movetime) = limits.movetime {
+            go.push_str(&format!(" movetime {}", movetime.as_millis()));
+        }
+        self.send(&go)?;
+
+        let movetime = limits
+            .movetime
+            .unwrap_or(Duration::from_millis(CONFIG.engine_movetime));
+        let deadline = Instant::now() + movetime + ANSWER_MARGIN;
+        let reply = match self.receive_best_move(deadline) {
+            Ok(reply) => reply,
+            // The engine has exited before the time was up
+            Err(err) if Instant::now() < deadline => return Err(err),
+            Err(_) => {
+                // The answer to `stop` is waited for, so that it is not taken for the next move
+                self.send("stop")?;
+                let _ = self.receive_best_move(Instant::now() + ANSWER_MARGIN);
+                return Err(err!(
+                    EngineError,
+                    "The engine did not answer within {} ms",
+                    (movetime + ANSWER_MARGIN).as_millis()
+                ));
+            }
+        };
+        game::Move::deserialize(reply.clone())
+            .filter(|&mv| board.is_legal(mv))
+            .ok_or(err!(
+                EngineError,
+                "The engine played an illegal move {}",
+                reply
+            ))
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        // Gives the engine a moment to quit by itself before it is killed
+        for _ in 0..10 {
+            if matches!(self.child.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+/// What the GUI asks for in `go`, times are in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GoOptions {
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub movetime: Option<u64>,
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub movestogo: Option<u64>,
+    // Search until told to stop
+    pub infinite: bool,
+}
+
+/// A line sent by the GUI to the engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UciCommand {
+    Uci,
+    IsReady,
+    NewGame,
+    SetOption(String, String),
+    // A FEN, or nothing for the starting position, and the moves played since in UCI notation
+    Position(Option<String>, Vec<String>),
+    Go(GoOptions),
+    Stop,
+    Quit,
+    // Anything else, which the protocol says to ignore
+    Unknown(String),
+}
+
+impl GoOptions {
+    /// Turns the options into search limits, sharing out the clock of `color` when there is no fixed limit
+    pub fn limits(&self, color: game::PieceColor) -> engine::SearchLimits {
+        let (time, increment) = match color {
+            game::PieceColor::White => (self.wtime, self.winc),
+            game::PieceColor::Black => (self.btime, self.binc),
+        };
+        let movetime = match (self.movetime, time) {
+            (Some(movetime), _) => Some(Duration::from_millis(movetime)),
+            (None, Some(time)) => {
+                engine::SearchLimits::clock(
+                    Duration::from_millis(time),
+                    Duration::from_millis(increment.unwrap_or(0)),
+                    self.movestogo,
+                )
+                .movetime
+            }
+            (None, None) => None,
+        };
+        engine::SearchLimits {
+            depth: self.depth,
+            movetime: movetime.filter(|_| !self.infinite),
+            nodes: self.nodes,
+        }
+    }
+}
+
+impl UciCommand {
+    pub fn parse(line: &str) -> Self {
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        match tokens.first().copied() {
+            Some("uci") => UciCommand::Uci,
+            Some("isready") => UciCommand::IsReady,
+            Some("ucinewgame") => UciCommand::NewGame,
+            Some("setoption") => Self::parse_option(&tokens[1..]),
+            Some("position") => Self::parse_position(&tokens[1..]),
+            Some("go") => UciCommand::Go(Self::parse_go(&tokens[1..])),
+            Some("stop") => UciCommand::Stop,
+            Some("quit") => UciCommand::Quit,
+            _ => UciCommand::Unknown(line.trim().to_string()),
+        }
+    }
+
+    // setoption name <name> [value <value>], both of which may contain spaces
+    fn parse_option(tokens: &[&str]) -> Self {
+        let value_at = tokens.iter().position(|&token| token == "value");
+        let name = tokens[..value_at.unwrap_or(tokens.len())]
+            .iter()
+            .skip_while(|&&token| token == "name")
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let value = value_at
+            .map(|at| tokens[at + 1..].join(" "))
+            .unwrap_or_default();
+        UciCommand::SetOption(name, value)
+    }
+
+    // position [startpos | fen <fen>] [moves <move>...]
+    fn parse_position(tokens: &[&str]) -> Self {
+        let moves_at = tokens
+            .iter()
+            .position(|&token| token == "moves")
+            .unwrap_or(tokens.len());
+        let fen = match tokens.first() {
+            Some(&"fen") => Some(tokens[1..moves_at].join(" ")),
+            _ => None,
+        };
+        let moves = tokens
+            .get(moves_at + 1..)
+            .unwrap_or_default()
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect();
+        UciCommand::Position(fen, moves)
+    }
+
+    fn parse_go(tokens: &[&str]) -> GoOptions {
+        let mut options = GoOptions::default();
+        let mut tokens = tokens.iter();
+        while let Some(&token) = tokens.next() {
+            match token {
+                "infinite" => {
+                    options.infinite = true;
+                    continue;
+                }
+                // Pondering is not supported, such a search simply runs on the position given
+                "ponder" => continue,
+                _ => {}
+            }
+            let Some(value) = tokens.next().and_then(|value| value.parse::<u64>().ok()) else {
+                continue;
+            };
+            match token {
+                "depth" => options.depth = Some(value as usize),
+                "nodes" => options.nodes = Some(value),
+                "movetime" => options.movetime = Some(value),
+                "wtime" => options.wtime = Some(value),
+                "btime" => options.btime = Some(value),
+                "winc" => options.winc = Some(value),
+                "binc" => options.binc = Some(value),
+                "movestogo" => options.movestogo = Some(value),
+                _ => {}
+            }
+        }
+        options
+    }
+}
@@ -0,0 +1,15 @@
+mod client;
+mod command;
+mod run;
+
+pub(super) use crate::game::{err, message_error};
+pub(super) use crate::prelude::*;
+pub use client::*;
+pub use command::*;
+pub use run::*;
+pub(super) use std::io::{BufRead, Write};
+pub(super) use std::process::{Child, ChildStdin, Command, Stdio};
+pub(super) use std::sync::atomic::{AtomicBool, Ordering};
+pub(super) use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+pub(super) use std::sync::Arc;
+pub(super) use std::time::Instant;
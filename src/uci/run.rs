@@ -0,0 +1,153 @@
+use super::*;
+
+/// A search running in the background, stopped by setting its flag
+struct Thinking {
+    stop: Arc<AtomicBool>,
+    handle: std::thread::JoinHandle<()>,
+}
+
+impl Thinking {
+    /// Searches on another thread so that the GUI can still be answered, printing `bestmove` when done
+    fn start(board: game::Board, options: GoOptions) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                let limits = options.limits(board.which_color());
+                let result = engine::search(&board, limits, &stop, |info| {
+                    println!("{}", info_line(info));
+                });
+
+                // An infinite search only answers once it is stopped, even when it has nothing left to look at
+                while options.infinite && !stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+
+                match result.and_then(|info| info.best_move()) {
+                    Some(mv) => println!("bestmove {}", mv),
+                    None => println!("bestmove 0000"),
+                }
+            }
+        });
+        Thinking { stop, handle }
+    }
+
+    fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.handle.join();
+    }
+}
+
+fn info_line(info: &engine::SearchInfo) -> String {
+    let score = match info.mate_in() {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", info.score),
+    };
+    let millis = info.time.as_millis() as u64;
+    format!(
+        "info depth {} score {} nodes {} nps {} time {} pv {}",
+        info.depth,
+        score,
+        info.nodes,
+        info.nodes * 1000 / millis.max(1),
+        millis,
+        info.pv
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
+
+/// Sets up the position given by the GUI, playing the moves from the start position or FEN
+fn position(
+    fen: Option<String>,
+    moves: &[String],
+    chess960: bool,
+) -> Result<game::Board, game::RecordError> {
+    let mut board = match fen {
+        Some(fen) => game::Board::from_fen(&fen)?,
+        None => game::Board::new(),
+    };
+    // Castling is then written as the king taking its own rook, even from the standard position
+    if chess960 {
+        board
+            .set_variant(game::Variant::Chess960)
+            .map_err(game::RecordError::Position)?;
+    }
+
+    for mv in moves {
+        let parsed = game::Move::deserialize(mv.clone())
+            .ok_or(game::RecordError::InvalidMove(mv.clone()))?;
+        board = board
+            .perform(parsed)
+            .map_err(|_| game::RecordError::IllegalMove(mv.clone()))?;
+    }
+    Ok(board)
+}
+
+/// Speaks the Universal Chess Interface over stdin and stdout until the GUI quits
+pub fn run_uci() {
+    let mut board = game::Board::new();
+    let mut chess960 = false;
+    let mut thinking: Option<Thinking> = None;
+
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        match UciCommand::parse(&line) {
+            UciCommand::Uci => {
+                println!("id name TermiChess {}", env!("CARGO_PKG_VERSION"));
+                println!("id author The TermiChess developers");
+                println!("option name UCI_Chess960 type check default false");
+                println!("uciok");
+            }
+            UciCommand::IsReady => println!("readyok"),
+            UciCommand::NewGame => {
+                if let Some(thinking) = thinking.take() {
+                    thinking.stop();
+                }
+                board = game::Board::new();
+            }
+            UciCommand::SetOption(name, value) => {
+                if name.eq_ignore_ascii_case("UCI_Chess960") {
+                    chess960 = value.eq_ignore_ascii_case("true");
+                } else {
+                    println!("info string Unknown option {}", name);
+                }
+            }
+            UciCommand::Position(fen, moves) => {
+                if let Some(thinking) = thinking.take() {
+                    thinking.stop();
+                }
+                match position(fen, &moves, chess960) {
+                    Ok(position) => board = position,
+                    Err(err) => println!("info string {}", err),
+                }
+            }
+            UciCommand::Go(options) => {
+                if let Some(thinking) = thinking.take() {
+                    thinking.stop();
+                }
+                thinking = Some(Thinking::start(board.clone(), options));
+            }
+            UciCommand::Stop => {
+                if let Some(thinking) = thinking.take() {
+                    thinking.stop();
+                }
+            }
+            UciCommand::Quit => break,
+            UciCommand::Unknown(command) => {
+                if !command.is_empty() {
+                    println!("info string Unknown command {}", command);
+                }
+            }
+        }
+    }
+
+    if let Some(thinking) = thinking.take() {
+        thinking.stop();
+    }
+}
@@ -1,10 +1,16 @@
 use super::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub unicode: bool,
     pub address: String,
     pub spacing: usize,
+    pub pgn_autosave: bool,
+    pub pgn_directory: String,
+    // An external UCI engine to play against, none if empty
+    pub engine_path: String,
+    pub engine_movetime: u64,
 }
 
 impl Default for Config {
@@ -13,6 +19,10 @@ impl Default for Config {
             unicode: false,
             address: "http://127.0.0.1:8080".to_string(),
             spacing: 3,
+            pgn_autosave: false,
+            pgn_directory: "games".to_string(),
+            engine_path: String::new(),
+            engine_movetime: 1000,
         }
     }
 }
@@ -3,23 +3,40 @@ pub enum Command {
     Chess(String),
     Resign,
     Draw,
+    Claim,
+    Fen,
     Chat(String),
+    // Takes back the last move, or plays it again, in local games
+    Undo,
+    Redo,
+    // Only ever sent by the server, when the opponent has run out of time
+    Timeout,
 }
 
 pub fn parse_raw(str: String) -> Command {
-    match str.as_str() {
+    let str = str.trim();
+    match str {
         "resign" | "quit" | "exit" => Command::Resign,
         "draw" => Command::Draw,
+        "claim" => Command::Claim,
+        "fen" => Command::Fen,
+        "undo" | "takeback" => Command::Undo,
+        "redo" => Command::Redo,
         _ => {
             if str.starts_with("chat") && str.len() >= 5 {
                 Command::Chat(str[4..].trim().to_string())
             } else {
-                Command::Chess(
-                    str.chars()
-                        .filter(|c| matches!(c, 'a'..='h' | '0'..='8' | 'Q' | 'R' | 'B' | 'N' | 'K' | 'x' | '='))
-                        .collect::<String>(),
-                )
+                // Moves are passed on untouched, `Board::translate` knows how to read them
+                Command::Chess(str.to_string())
             }
         }
     }
 }
+
+/// Reads a command relayed by the server, which may also be a timeout it called itself
+pub fn parse_remote(str: String) -> Command {
+    match str.trim() {
+        "timeout" => Command::Timeout,
+        _ => parse_raw(str),
+    }
+}
@@ -1,6 +1,10 @@
 mod config;
 mod input;
+mod pgn;
+mod setup;
 
 pub(super) use crate::prelude::*;
 pub use config::*;
 pub use input::*;
+pub use pgn::*;
+pub use setup::*;
@@ -0,0 +1,42 @@
+use super::*;
+
+/// Writes the game into a new PGN file under the configured directory
+pub fn save_pgn(board: &game::Board, info: &game::GameInfo) -> std::io::Result<std::path::PathBuf> {
+    let dir = std::path::PathBuf::from(&CONFIG.pgn_directory);
+    std::fs::create_dir_all(&dir)?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let mut path = dir.join(format!("termichess-{}.pgn", timestamp));
+    let mut index = 1;
+    while path.exists() {
+        path = dir.join(format!("termichess-{}-{}.pgn", timestamp, index));
+        index += 1;
+    }
+
+    let pgn = board
+        .to_pgn(info)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    std::fs::write(&path, pgn)?;
+    Ok(path)
+}
+
+/// Saves a finished game, either automatically or when the player agrees to
+pub fn offer_pgn_save(board: &game::Board, info: &game::GameInfo) {
+    let save = CONFIG.pgn_autosave
+        || dialoguer::Confirm::new()
+            .with_prompt("Do you want to save this game as PGN?")
+            .default(false)
+            .interact()
+            .unwrap();
+    if !save {
+        return;
+    }
+
+    match save_pgn(board, info) {
+        Ok(path) => println!("Game saved to {}", style(path.display()).bold()),
+        Err(err) => println!("{}: Failed to save the game: {}", style("Error").red(), err),
+    }
+}
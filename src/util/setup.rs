@@ -0,0 +1,82 @@
+use super::*;
+
+/// Asks which variant to play and returns its starting position
+pub fn choose_variant(prompt: &str) -> game::Board {
+    let variant = dialoguer::Select::new()
+        .with_prompt(prompt)
+        .items(&["Standard", "Chess960"])
+        .default(0)
+        .interact()
+        .unwrap();
+    match variant {
+        0 => game::Board::new(),
+        _ => choose_chess960(),
+    }
+}
+
+/// Asks for the number of a Chess960 starting position, a random one is drawn when left empty
+pub fn choose_chess960() -> game::Board {
+    loop {
+        let id = dialoguer::Input::<String>::new()
+            .with_prompt(format!(
+                "Starting position(0-{}, leave empty for a random one)",
+                game::CHESS960_POSITIONS - 1
+            ))
+            .allow_empty(true)
+            .interact()
+            .unwrap();
+        if id.trim().is_empty() {
+            return game::Board::new_chess960_random();
+        }
+        match id.trim().parse().ok().and_then(game::Board::new_chess960) {
+            Some(board) => return board,
+            None => println!(
+                "{}: \"{}\" is not a Chess960 starting position",
+                style("Error").red(),
+                id.trim()
+            ),
+        }
+    }
+}
+
+// Common time controls, as written for `TimeControl`
+const TIME_CONTROLS: [(&str, &str); 6] = [
+    ("Bullet", "1+0"),
+    ("Blitz", "3+2"),
+    ("Blitz", "5+0"),
+    ("Rapid", "10+5"),
+    ("Rapid", "15+10"),
+    ("Classical", "40/90+30, 30+30"),
+];
+
+/// Asks whether to play on the clock and with which time control
+pub fn choose_time_control(prompt: &str) -> Option<game::TimeControl> {
+    let mut items = vec!["No clock".to_string()];
+    items.extend(
+        TIME_CONTROLS
+            .iter()
+            .map(|(name, control)| format!("{}({})", name, control)),
+    );
+    items.push("Custom".to_string());
+
+    let choice = dialoguer::Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()
+        .unwrap();
+    match choice {
+        0 => None,
+        i if i <= TIME_CONTROLS.len() => TIME_CONTROLS[i - 1].1.parse().ok(),
+        _ => loop {
+            let control = dialoguer::Input::<String>::new()
+                .with_prompt("Time control(e.g. \"5+3\", \"15d5\" or \"40/90+30, 30+30\")")
+                .interact()
+                .unwrap();
+            match control.parse() {
+                Ok(control) => break Some(control),
+                Err(err) => println!("{}: {}", style("Error").red(), err),
+            }
+        },
+    }
+}
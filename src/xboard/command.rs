@@ -0,0 +1,99 @@
+use super::*;
+
+/// A line sent by an xboard-style interface to the engine, in the Chess Engine Communication Protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XboardCommand {
+    Xboard,
+    Protover(u32),
+    New,
+    Variant(String),
+    // Stop playing either side, moves are only recorded
+    Force,
+    // Play the side to move, starting now
+    Go,
+    // Play the side that is not to move
+    PlayOther,
+    UserMove(String),
+    SetBoard(String),
+    // The engine's and the opponent's clocks, in centiseconds
+    Time(u64),
+    Otim(u64),
+    // Moves per time control (0 for all of them), base time and increment
+    Level(u64, Duration, Duration),
+    // A fixed time per move
+    St(Duration),
+    // A fixed search depth
+    Sd(usize),
+    Post(bool),
+    Ping(String),
+    Draw,
+    Result(String),
+    Undo,
+    Remove,
+    Quit,
+    // Commands that need no answer and change nothing here
+    Ignored,
+    // Anything else, which may be a move from an interface that does not prefix them
+    Unknown(String),
+}
+
+// Seconds, possibly with a fraction as in `st 0.5`
+fn parse_seconds(seconds: &str) -> Option<Duration> {
+    Duration::try_from_secs_f64(seconds.parse().ok()?).ok()
+}
+
+// Minutes, or minutes and seconds as in `level 40 5:30 0`
+fn parse_minutes(minutes: &str) -> Option<Duration> {
+    match minutes.split_once(':') {
+        Some((minutes, seconds)) => Some(
+            Duration::from_secs(minutes.parse::<u64>().ok()? * 60)
+                + Duration::from_secs(seconds.parse().ok()?),
+        ),
+        None => Some(Duration::from_secs(minutes.parse::<u64>().ok()? * 60)),
+    }
+}
+
+impl XboardCommand {
+    pub fn parse(line: &str) -> Self {
+        let line = line.trim();
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        let args = args.trim();
+        let tokens = args.split_whitespace().collect::<Vec<_>>();
+
+        let parsed = match command {
+            "xboard" => Some(XboardCommand::Xboard),
+            "protover" => args.parse().ok().map(XboardCommand::Protover),
+            "new" => Some(XboardCommand::New),
+            "variant" => Some(XboardCommand::Variant(args.to_string())),
+            "force" => Some(XboardCommand::Force),
+            "go" => Some(XboardCommand::Go),
+            "playother" => Some(XboardCommand::PlayOther),
+            "usermove" => Some(XboardCommand::UserMove(args.to_string())),
+            "setboard" => Some(XboardCommand::SetBoard(args.to_string())),
+            "time" => args.parse().ok().map(XboardCommand::Time),
+            "otim" => args.parse().ok().map(XboardCommand::Otim),
+            "level" => match tokens[..] {
+                [moves, base, increment] => Some(XboardCommand::Level(
+                    moves.parse().unwrap_or(0),
+                    parse_minutes(base).unwrap_or_default(),
+                    parse_seconds(increment).unwrap_or_default(),
+                )),
+                _ => None,
+            },
+            "st" => parse_seconds(args).map(XboardCommand::St),
+            "sd" => args.parse().ok().map(XboardCommand::Sd),
+            "post" => Some(XboardCommand::Post(true)),
+            "nopost" => Some(XboardCommand::Post(false)),
+            "ping" => Some(XboardCommand::Ping(args.to_string())),
+            "draw" => Some(XboardCommand::Draw),
+            "result" => Some(XboardCommand::Result(args.to_string())),
+            "undo" => Some(XboardCommand::Undo),
+            "remove" => Some(XboardCommand::Remove),
+            "quit" => Some(XboardCommand::Quit),
+            "accepted" | "rejected" | "random" | "hard" | "easy" | "computer" | "name"
+            | "rating" | "ics" | "." | "" => Some(XboardCommand::Ignored),
+            _ => None,
+        };
+        parsed.unwrap_or(XboardCommand::Unknown(line.to_string()))
+    }
+}
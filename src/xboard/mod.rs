@@ -0,0 +1,7 @@
+mod command;
+mod run;
+
+pub(super) use crate::prelude::*;
+pub use command::*;
+pub use run::*;
+pub(super) use std::io::{BufRead, Write};
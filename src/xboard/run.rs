@@ -0,0 +1,271 @@
+use super::*;
+
+// Used when the interface has set no time control at all
+const DEFAULT_MOVETIME: Duration = Duration::from_secs(1);
+
+/// Everything the interface has set up, the board and the clocks, and where to answer it
+struct Session<W: Write> {
+    out: W,
+    board: game::Board,
+    // The positions before each move, for `undo`
+    history: Vec<game::Board>,
+    // The side the engine plays, none in force mode
+    engine: Option<game::PieceColor>,
+    chess960: bool,
+    time: Option<Duration>,
+    // Moves per time control, or 0 when the whole game is one control
+    moves_per_control: u64,
+    increment: Duration,
+    movetime: Option<Duration>,
+    depth: Option<usize>,
+    post: bool,
+}
+
+impl<W: Write> Session<W> {
+    fn new(out: W) -> Self {
+        Session {
+            out,
+            board: game::Board::new(),
+            history: Vec::new(),
+            engine: Some(game::PieceColor::Black),
+            chess960: false,
+            time: None,
+            moves_per_control: 0,
+            increment: Duration::ZERO,
+            movetime: None,
+            depth: None,
+            post: false,
+        }
+    }
+
+    /// Sends a line to the interface, which reads nothing until the line is complete
+    fn send(&mut self, line: impl Display) {
+        let _ = writeln!(self.out, "{}", line).and_then(|_| self.out.flush());
+    }
+
+    fn limits(&self) -> engine::SearchLimits {
+        let mut limits = match (self.movetime, self.time) {
+            (Some(movetime), _) => engine::SearchLimits::movetime(movetime),
+            (None, Some(time)) => {
+                let moves_to_go = (self.moves_per_control > 0).then(|| {
+                    let played = (self.board.turn as u64 - 1) / 2;
+                    self.moves_per_control - played % self.moves_per_control
+                });
+                engine::SearchLimits::clock(time, self.increment, moves_to_go)
+            }
+            (None, None) if self.depth.is_some() => engine::SearchLimits::default(),
+            (None, None) => engine::SearchLimits::movetime(DEFAULT_MOVETIME),
+        };
+        limits.depth = self.depth;
+        limits
+    }
+
+    fn play(&mut self, mv: game::Move) -> Result<(), game::MoveError> {
+        let next = self.board.perform(mv)?;
+        self.history.push(std::mem::replace(&mut self.board, next));
+        if self.board.status != game::Status::Playing {
+            self.engine = None;
+            let line = result_line(&self.board);
+            self.send(line);
+        }
+        Ok(())
+    }
+
+    /// Thinks about the position and plays the best move found
+    fn think(&mut self) {
+        let post = self.post;
+        let limits = self.limits();
+        let out = &mut self.out;
+        let result = engine::search(
+            &self.board,
+            limits,
+            &std::sync::atomic::AtomicBool::new(false),
+            |info| {
+                if post {
+                    let _ = writeln!(out, "{}", thinking_line(info));
+                }
+            },
+        );
+        let Some(mv) = result.and_then(|info| info.best_move()) else {
+            return;
+        };
+
+        // Castling is written as in PGN in Chess960, since the king may not move at all
+        let notation = match self.board.castle_side(mv) {
+            Some(game::CastleSide::King) if self.chess960 => "O-O".to_string(),
+            Some(game::CastleSide::Queen) if self.chess960 => "O-O-O".to_string(),
+            _ => mv.to_string(),
+        };
+        self.send(format!("move {}", notation));
+        self.play(mv).unwrap();
+    }
+}
+
+// depth, score in centipawns, time in centiseconds, nodes and the expected line
+fn thinking_line(info: &engine::SearchInfo) -> String {
+    // Mates are written as 100000 plus the number of moves, as xboard expects
+    let score = match info.mate_in() {
+        Some(moves) if moves > 0 => 100000 + moves,
+        Some(moves) => -100000 + moves,
+        None => info.score,
+    };
+    format!(
+        "{} {} {} {} {}",
+        info.depth,
+        score,
+        info.time.as_millis() / 10,
+        info.nodes,
+        info.pv
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
+
+fn result_line(board: &game::Board) -> String {
+    let reason = board
+        .termination
+        .map(|termination| termination.name())
+        .unwrap_or("Game over");
+    format!("{} {{{}}}", board.status.to_pgn(), reason)
+}
+
+/// Speaks the Chess Engine Communication Protocol over stdin and stdout until the interface quits
+pub fn run_xboard() {
+    run_xboard_with(std::io::stdin().lock(), std::io::stdout().lock());
+}
+
+/// Answers the commands read from `input` on `output` until the interface quits or the input ends.
+///
+/// The engine thinks while the input waits, so commands sent meanwhile are answered after its move
+pub fn run_xboard_with(input: impl BufRead, output: impl Write) {
+    let mut session = Session::new(output);
+
+    for line in input.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        match XboardCommand::parse(&line) {
+            XboardCommand::Xboard | XboardCommand::Ignored => {}
+            XboardCommand::Protover(_) => {
+                session.send(format!(
+                    "feature myname=\"TermiChess {}\" usermove=1 setboard=1 ping=1 playother=1 san=0 colors=0 sigint=0 sigterm=0 variants=\"normal,fischerandom\" done=1",
+                    env!("CARGO_PKG_VERSION")
+                ));
+            }
+            // The time control and thinking output are kept for the next game
+            XboardCommand::New => {
+                session.board = game::Board::new();
+                session.history.clear();
+                session.engine = Some(game::PieceColor::Black);
+                session.chess960 = false;
+                session.depth = None;
+            }
+            XboardCommand::Variant(variant) => {
+                let variant = match variant.as_str() {
+                    "fischerandom" => game::Variant::Chess960,
+                    "normal" => game::Variant::Standard,
+                    _ => {
+                        session.send(format!("Error (unsupported variant): {}", variant));
+                        continue;
+                    }
+                };
+                session.chess960 = variant == game::Variant::Chess960;
+                if let Err(problems) = session.board.set_variant(variant) {
+                    session.send(format!(
+                        "tellusererror Illegal position: {}",
+                        game::describe_problems(&problems)
+                    ));
+                }
+            }
+            XboardCommand::Force => session.engine = None,
+            XboardCommand::Go => session.engine = Some(session.board.which_color()),
+            XboardCommand::PlayOther => {
+                session.engine = Some(session.board.which_color().opposite())
+            }
+            XboardCommand::UserMove(mv) => {
+                let played = session
+                    .board
+                    .translate(&mv)
+                    .and_then(|translated| session.play(translated));
+                if let Err(err) = played {
+                    session.send(format!("Illegal move ({}): {}", err, mv));
+                }
+            }
+            XboardCommand::SetBoard(fen) => {
+                let board = game::Board::from_fen(&fen)
+                    .map_err(|err| err.to_string())
+                    .and_then(|mut board| {
+                        if session.chess960 {
+                            board
+                                .set_variant(game::Variant::Chess960)
+                                .map_err(|problems| game::describe_problems(&problems))?;
+                        }
+                        Ok(board)
+                    });
+                match board {
+                    Ok(board) => {
+                        session.board = board;
+                        session.history.clear();
+                    }
+                    Err(err) => session.send(format!("tellusererror Illegal position: {}", err)),
+                }
+            }
+            XboardCommand::Time(centiseconds) => {
+                session.time = Some(Duration::from_millis(centiseconds * 10))
+            }
+            XboardCommand::Otim(_) => {}
+            XboardCommand::Level(moves, base, increment) => {
+                session.time = Some(base);
+                session.moves_per_control = moves;
+                session.increment = increment;
+                session.movetime = None;
+            }
+            XboardCommand::St(movetime) => session.movetime = Some(movetime),
+            XboardCommand::Sd(depth) => session.depth = Some(depth),
+            XboardCommand::Post(post) => session.post = post,
+            XboardCommand::Ping(id) => session.send(format!("pong {}", id)),
+            XboardCommand::Draw => {
+                // Only a worse position is worth a draw, the offer may come on either side's turn
+                if let Some(color) = session.engine {
+                    if engine::evaluate_for(&session.board, color) < 0 {
+                        session.send("offer draw");
+                    }
+                }
+            }
+            XboardCommand::Result(_) => session.engine = None,
+            XboardCommand::Undo => {
+                if let Some(board) = session.history.pop() {
+                    session.board = board;
+                }
+            }
+            XboardCommand::Remove => {
+                for _ in 0..2 {
+                    if let Some(board) = session.history.pop() {
+                        session.board = board;
+                    }
+                }
+            }
+            XboardCommand::Quit => break,
+            XboardCommand::Unknown(command) => {
+                // Interfaces that ignore `usermove=1` send bare moves
+                match session.board.translate(&command) {
+                    Ok(mv) => {
+                        if let Err(err) = session.play(mv) {
+                            session.send(format!("Illegal move ({}): {}", err, command));
+                        }
+                    }
+                    Err(_) => session.send(format!("Error (unknown command): {}", command)),
+                }
+            }
+        }
+
+        if session.engine == Some(session.board.which_color())
+            && session.board.status == game::Status::Playing
+        {
+            session.think();
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::{Duration, Instant};
+
+use termichess::game::{Board, Bonus, Clock, GameInfo, Status, Termination, TimeControl};
+use termichess::util::{parse_raw, parse_remote, Command};
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+
+fn new_clock(control: &str) -> Clock {
+    Clock::new(control.parse().unwrap())
+}
+
+#[test]
+fn parse_time_controls() {
+    let control: TimeControl = "40/90+30, 30+30".parse().unwrap();
+    assert_eq!(control.stages.len(), 2);
+    assert_eq!(control.stages[0].moves, Some(40));
+    assert_eq!(control.stages[0].time, secs(90 * 60));
+    assert_eq!(control.stages[0].bonus, Bonus::Increment(secs(30)));
+    assert_eq!(control.stages[1].moves, None);
+    assert_eq!(control.to_string(), "40/90+30, 30+30");
+
+    let control: TimeControl = "0.5".parse().unwrap();
+    assert_eq!(control.stages[0].time, secs(30));
+    assert_eq!(control.stages[0].bonus, Bonus::None);
+    assert_eq!(
+        "15d5".parse::<TimeControl>().unwrap().stages[0].bonus,
+        Bonus::Delay(secs(5))
+    );
+    assert_eq!(
+        "15b5".parse::<TimeControl>().unwrap().stages[0].bonus,
+        Bonus::Bronstein(secs(5))
+    );
+
+    for invalid in ["", "0", "5+", "x/5", "0/5", "5+-1", "40/"] {
+        assert!(invalid.parse::<TimeControl>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn sudden_death_and_increment() {
+    let now = Instant::now();
+    let mut clock = new_clock("1");
+    clock.start(true, now);
+    assert!(clock.press(now + secs(20)));
+    assert_eq!(clock.left(true, now + secs(100)), secs(40));
+    assert_eq!(clock.left(false, now + secs(30)), secs(50));
+    assert!(clock.is_flagged(false, now + secs(80)));
+    assert!(!clock.press(now + secs(80)));
+    assert_eq!(clock.running(), None);
+
+    let mut clock = new_clock("1+5");
+    clock.start(true, now);
+    assert!(clock.press(now + secs(10)));
+    assert_eq!(clock.left(true, now + secs(10)), secs(55));
+}
+
+#[test]
+fn delays() {
+    let now = Instant::now();
+
+    // Simple delay, the first seconds of each move are free
+    let mut clock = new_clock("1d5");
+    clock.start(true, now);
+    assert_eq!(clock.left(true, now + secs(3)), secs(60));
+    assert_eq!(clock.left(true, now + secs(8)), secs(57));
+    assert!(clock.press(now + secs(8)));
+    assert_eq!(clock.left(true, now + secs(8)), secs(57));
+
+    // Bronstein delay, the time used is given back up to the delay
+    let mut clock = new_clock("1b5");
+    clock.start(true, now);
+    assert!(clock.press(now + secs(3)));
+    assert_eq!(clock.left(true, now + secs(3)), secs(60));
+    assert!(clock.press(now + secs(13)));
+    assert!(clock.press(now + secs(23)));
+    assert_eq!(clock.left(true, now + secs(23)), secs(55));
+}
+
+#[test]
+fn multi_stage() {
+    let now = Instant::now();
+    let mut clock = new_clock("2/1, 1");
+    clock.start(true, now);
+    let mut time = now;
+    for _ in 0..4 {
+        time += secs(10);
+        assert!(clock.press(time));
+    }
+    // White made both moves of the first stage and got the next minute
+    assert_eq!(clock.left(true, time), secs(100));
+    assert_eq!(clock.left(false, time), secs(100));
+
+    // The last stage repeats once reached
+    let mut clock = new_clock("1/1");
+    clock.start(true, now);
+    assert!(clock.press(now + secs(50)));
+    assert_eq!(clock.left(true, now + secs(50)), secs(70));
+}
+
+#[test]
+fn server_state() {
+    let now = Instant::now();
+    let mut server = new_clock("5+3");
+    server.start(true, now);
+    assert!(server.press(now + secs(30)));
+
+    let state = server.state(now + secs(40));
+    assert_eq!(state.white, 273_000);
+    assert_eq!(state.black, 290_000);
+    assert_eq!(state.running, Some(false));
+
+    let mut client = new_clock("5+3");
+    client.set_state(&state, now + secs(41));
+    assert_eq!(client.left(false, now + secs(45)), secs(286));
+    assert_eq!(client.left(true, now + secs(45)), secs(273));
+}
+
+#[test]
+fn flag_fall() {
+    let mut board = Board::new();
+    board.set_time_control("5+3".parse().unwrap());
+    board.sync_clock();
+    assert!(!board.check_flag());
+
+    board.flag(board.which_color());
+    assert_eq!(board.status, Status::Black);
+    assert_eq!(board.termination, Some(Termination::Timeout));
+    assert!(board
+        .to_pgn(&GameInfo::new("Test", "White", "Black"))
+        .unwrap()
+        .contains("[TimeControl \"300+3\"]"));
+
+    // A lone king cannot win on time
+    let mut board = Board::from_fen("8/8/4k3/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    board.flag(board.which_color());
+    assert_eq!(board.status, Status::Draw);
+
+    let mut board = Board::from_fen("8/8/4k3/8/8/8/4N3/4K3 b - - 0 1").unwrap();
+    board.flag(board.which_color());
+    assert_eq!(board.status, Status::Draw);
+}
+
+#[test]
+fn only_the_server_calls_timeouts() {
+    // A player typing it is read as a move, which is then rejected
+    assert_eq!(
+        parse_raw("timeout".to_string()),
+        Command::Chess("timeout".to_string())
+    );
+    assert_eq!(parse_remote(" timeout ".to_string()), Command::Timeout);
+    assert_eq!(
+        parse_remote("e2e4".to_string()),
+        Command::Chess("e2e4".to_string())
+    );
+}
@@ -0,0 +1,138 @@
+use termichess::game::{Board, Status, Termination};
+
+fn play(board: &Board, moves: &[&str]) -> Board {
+    let mut board = board.clone();
+    for mv in moves {
+        board = board.perform(board.translate(mv).unwrap()).unwrap();
+    }
+    board
+}
+
+const SHUFFLE: [&str; 4] = ["Nf3", "Nf6", "Ng1", "Ng8"];
+
+#[test]
+fn repetition() {
+    let mut board = Board::new();
+    for occurrence in 2..=5 {
+        board = play(&board, &SHUFFLE);
+        assert_eq!(board.repetitions(), occurrence);
+        assert_eq!(board.hash(), Board::new().hash());
+
+        let mut claimed = board.clone();
+        match occurrence {
+            2 => assert!(!claimed.claim_draw()),
+            3 | 4 => {
+                assert_eq!(board.status, Status::Playing);
+                assert!(claimed.claim_draw());
+                assert_eq!(claimed.termination, Some(Termination::Repetition));
+            }
+            _ => {}
+        }
+    }
+
+    // Drawn without a claim on the fifth occurrence
+    assert_eq!(board.status, Status::Draw);
+    assert_eq!(board.termination, Some(Termination::Repetition));
+}
+
+#[test]
+fn hash() {
+    // The same position reached in another order hashes the same
+    let a = play(&Board::new(), &["e4", "e5", "Nf3", "Nc6"]);
+    let b = play(&Board::new(), &["Nf3", "Nc6", "e4", "e5"]);
+    assert_eq!(a.hash(), b.hash());
+    assert_ne!(a.hash(), Board::new().hash());
+
+    // Only when the castling rights match as well, not just the pieces
+    let c = play(&a, &["Ke2", "Ke7", "Ke1", "Ke8"]);
+    assert_ne!(c.hash(), a.hash());
+
+    let kiwipete =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    for board in [Board::new(), a, kiwipete] {
+        let hash = board.hash();
+        for mv in board.legal_moves() {
+            let mut made = board.clone();
+            let undo = made.make_move(mv);
+            assert_ne!(made.hash(), hash, "{}", mv);
+            made.unmake_move(undo);
+            assert_eq!(made.hash(), hash, "{}", mv);
+        }
+    }
+}
+
+fn at_halfmove(halfmove: usize) -> Board {
+    Board::from_fen(&format!("4k3/8/8/8/8/8/r3P3/R3K3 w - - {} 80", halfmove)).unwrap()
+}
+
+#[test]
+fn fifty_moves() {
+    let mut board = at_halfmove(98);
+    assert!(!board.is_fifty_moves());
+    assert!(!board.claim_draw());
+
+    // A hundred half moves without a capture or a pawn move can be claimed
+    let mut board = play(&board, &["Rb1"]);
+    assert_eq!(board.halfmove, 99);
+    assert!(!board.claim_draw());
+    let mut board = play(&board, &["Rb2"]);
+    assert_eq!(board.halfmove, 100);
+    assert_eq!(board.status, Status::Playing);
+    assert!(board.claim_draw());
+    assert_eq!(board.status, Status::Draw);
+    assert_eq!(board.termination, Some(Termination::FiftyMoves));
+
+    // Pawn moves and captures start the count again
+    assert_eq!(play(&at_halfmove(99), &["e3"]).halfmove, 0);
+    assert_eq!(play(&at_halfmove(99), &["e4"]).halfmove, 0);
+    assert_eq!(play(&at_halfmove(99), &["Rxa2"]).halfmove, 0);
+    let mut board = play(&at_halfmove(120), &["Rxa2"]);
+    assert!(!board.claim_draw());
+}
+
+#[test]
+fn seventy_five_moves() {
+    let board = play(&at_halfmove(148), &["Rb1"]);
+    assert_eq!(board.halfmove, 149);
+    assert_eq!(board.status, Status::Playing);
+
+    // Drawn without a claim after a hundred and fifty half moves
+    let board = play(&board, &["Rb2"]);
+    assert_eq!(board.halfmove, 150);
+    assert_eq!(board.status, Status::Draw);
+    assert_eq!(board.termination, Some(Termination::FiftyMoves));
+
+    // Unless the last move mates
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 149 120").unwrap();
+    let board = play(&board, &["Ra8"]);
+    assert_eq!(board.status, Status::White);
+    assert_eq!(board.termination, Some(Termination::Checkmate));
+}
+
+fn is_insufficient(fen: &str) -> bool {
+    Board::from_fen(fen).unwrap().is_insufficient_material()
+}
+
+#[test]
+fn insufficient_material() {
+    assert!(is_insufficient("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+    assert!(is_insufficient("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"));
+    assert!(is_insufficient("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+    // Bishops on the same color, whoever owns them
+    assert!(is_insufficient("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+
+    // A mate can still be set up, however unlikely
+    assert!(!is_insufficient("4kn2/8/8/8/8/8/8/4KN2 w - - 0 1"));
+    assert!(!is_insufficient("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+    assert!(!is_insufficient("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1"));
+    assert!(!is_insufficient("4k3/8/8/8/8/8/8/2B1KN2 w - - 0 1"));
+    assert!(!is_insufficient("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"));
+
+    // Drawn as soon as the last piece that could mate is taken
+    let board = Board::from_fen("4k3/8/8/8/8/8/3r4/4KN2 w - - 0 1").unwrap();
+    assert_eq!(board.status, Status::Playing);
+    let board = play(&board, &["Kxd2"]);
+    assert_eq!(board.status, Status::Draw);
+    assert_eq!(board.termination, Some(Termination::InsufficientMaterial));
+}
@@ -0,0 +1,74 @@
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+use termichess::engine::{best_move, evaluate, evaluate_for, search, SearchLimits};
+use termichess::game::{Board, PieceColor};
+
+fn best(fen: &str, depth: usize) -> String {
+    let board = Board::from_fen(fen).unwrap();
+    best_move(&board, SearchLimits::depth(depth))
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn finds_mate_in_one() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    let info = search(
+        &board,
+        SearchLimits::depth(3),
+        &AtomicBool::new(false),
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(info.best_move().unwrap().to_string(), "a1a8");
+    assert_eq!(info.mate_in(), Some(1));
+}
+
+#[test]
+fn takes_hanging_piece() {
+    assert_eq!(best("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1", 2), "d1d5");
+}
+
+#[test]
+fn avoids_losing_the_queen() {
+    // Taking the defended pawn loses the queen to the rook
+    assert_ne!(best("3rk3/8/3p4/8/8/8/8/3QK3 w - - 0 1", 3), "d1d6");
+}
+
+#[test]
+fn respects_limits() {
+    let board = Board::new();
+    let start = Instant::now();
+    let info = search(
+        &board,
+        SearchLimits::movetime(Duration::from_millis(200)),
+        &AtomicBool::new(false),
+        |_| {},
+    )
+    .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(board.is_legal(info.best_move().unwrap()));
+
+    // A stopped search still returns the first depth
+    let info = search(
+        &board,
+        SearchLimits::default(),
+        &AtomicBool::new(true),
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(info.depth, 1);
+
+    // Nothing to play once the game is over
+    let mate = Board::from_fen("6k1/5ppp/8/8/8/8/r4PPP/r5K1 w - - 0 1").unwrap();
+    assert!(best_move(&mate, SearchLimits::depth(2)).is_none());
+}
+
+#[test]
+fn scores_for_either_side() {
+    // White is a queen up, with Black to move
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+    assert!(evaluate(&board) < 0);
+    assert!(evaluate_for(&board, PieceColor::White) > 0);
+    assert!(evaluate_for(&board, PieceColor::Black) < 0);
+}
@@ -0,0 +1,32 @@
+use termichess::game::{Board, START_FEN};
+
+#[test]
+fn round_trip() {
+    for fen in [
+        START_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "8/8/8/4k3/8/8/8/R3K3 b Q - 12 40",
+    ] {
+        assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+    }
+}
+
+#[test]
+fn invalid_fullmove_numbers() {
+    for fullmove in [
+        "0",
+        "-1",
+        "x",
+        "9999999999999999999",
+        "18446744073709551615",
+    ] {
+        let fen = format!("4k3/8/8/8/8/8/8/4K3 b - - 0 {}", fullmove);
+        let err = Board::from_fen(&fen).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid fullmove number"),
+            "{}",
+            fen
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use termichess::game::{Board, FenError, MoveError, NotationError};
+
+fn error(board: &Board, notation: &str) -> MoveError {
+    board.translate(notation).unwrap_err()
+}
+
+#[test]
+fn notation_errors() {
+    let board = Board::new();
+    assert_eq!(
+        error(&board, "Xe4"),
+        MoveError::Notation(NotationError::InvalidPiece('X'))
+    );
+    assert_eq!(
+        error(&board, "Ne"),
+        MoveError::Notation(NotationError::BadSquare("e".to_string()))
+    );
+    assert_eq!(
+        error(&board, "Nd4"),
+        MoveError::Notation(NotationError::NoMatch)
+    );
+    assert_eq!(
+        error(&board, "Nxf3"),
+        MoveError::Notation(NotationError::NothingToTake)
+    );
+
+    let board = ["e4", "e5", "Nc3", "Nc6"]
+        .iter()
+        .fold(Board::new(), |board, mv| {
+            board.perform(board.translate(mv).unwrap()).unwrap()
+        });
+    assert_eq!(
+        error(&board, "Ne2"),
+        MoveError::Notation(NotationError::Ambiguous)
+    );
+    assert!(board.translate("Nge2").is_ok());
+
+    let board = Board::from_fen("8/4P3/8/8/8/8/k7/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        error(&board, "e8=K"),
+        MoveError::Notation(NotationError::InvalidPromotion("K".to_string()))
+    );
+    assert!(board.translate("e8=Q").is_ok());
+}
+
+#[test]
+fn message_errors() {
+    let err: FenError = Board::from_fen("8/8/8 w - - 0 1").unwrap_err();
+    assert!(!err.msg.is_empty());
+    assert_eq!(err.to_string(), err.msg);
+}
+
+fn uci(board: &Board, notation: &str) -> String {
+    board.translate(notation).unwrap().to_string()
+}
+
+#[test]
+fn translate_inputs() {
+    let board = Board::new();
+    for notation in ["e4", "e2e4", "e2-e4", "Pe4", " e4 ", "e4!?", "e4!!", "e4?"] {
+        assert_eq!(uci(&board, notation), "e2e4", "{}", notation);
+    }
+    assert_eq!(uci(&board, "Nf3"), "g1f3");
+    assert_eq!(uci(&board, "Ng1f3"), "g1f3");
+
+    // Promotions as in SAN and in UCI
+    let board = Board::from_fen("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    for notation in ["e8=Q", "e8Q", "e7e8q", "e8=Q+", "e8q"] {
+        assert_eq!(uci(&board, notation), "e7e8q", "{}", notation);
+    }
+    assert_eq!(uci(&board, "exd8=N"), "e7d8n");
+    assert_eq!(uci(&board, "e7d8r"), "e7d8r");
+    assert_eq!(error(&board, "e8"), MoveError::MissingPromotion);
+
+    // Castling with letters or zeros, with or without dashes
+    let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    for notation in ["O-O", "0-0", "o-o", "OO", "O-O+"] {
+        assert_eq!(uci(&board, notation), "e1g1", "{}", notation);
+    }
+    for notation in ["O-O-O", "0-0-0", "o-o-o"] {
+        assert_eq!(uci(&board, notation), "e1c1", "{}", notation);
+    }
+
+    // En passant, with or without its suffix
+    let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    for notation in ["exd6", "exd6e.p.", "exd6 e.p.", "e5d6", "ed6"] {
+        assert_eq!(uci(&board, notation), "e5d6", "{}", notation);
+    }
+}
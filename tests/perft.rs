@@ -0,0 +1,106 @@
+use termichess::game::{chess960_back_rank, Board, CHESS960_POSITIONS};
+
+fn check(fen: &str, expected: &[u64]) {
+    let board = Board::from_fen(fen).unwrap();
+    for (depth, &nodes) in expected.iter().enumerate() {
+        assert_eq!(
+            board.perft(depth + 1),
+            nodes,
+            "depth {} of {}",
+            depth + 1,
+            fen
+        );
+    }
+}
+
+#[test]
+fn start_position() {
+    check(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[20, 400, 8902, 197281],
+    );
+    assert_eq!(Board::new().perft(3), 8902);
+}
+
+#[test]
+fn kiwipete() {
+    check(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2039, 97862],
+    );
+}
+
+#[test]
+fn en_passant_and_pins() {
+    check(
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2812, 43238],
+    );
+}
+
+#[test]
+fn promotions() {
+    check(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9467],
+    );
+    check(
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        &[6, 264, 9467],
+    );
+}
+
+#[test]
+fn castling_and_checks() {
+    check(
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        &[44, 1486, 62379],
+    );
+    check(
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        &[46, 2079, 89890],
+    );
+}
+
+#[test]
+fn divide_matches_perft() {
+    let board = Board::new();
+    let divide = board.divide(2);
+    assert_eq!(divide.len(), 20);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 400);
+}
+
+#[test]
+fn chess960() {
+    check(
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        &[21, 528, 12189],
+    );
+    check(
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        &[21, 807, 18002],
+    );
+    check(
+        "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        &[20, 479, 10471],
+    );
+
+    let standard = Board::new_chess960(518).unwrap();
+    assert_eq!(standard.to_fen(), Board::new().to_fen());
+    assert_eq!(standard.perft(3), 8902);
+}
+
+#[test]
+fn chess960_start_positions() {
+    let mut back_ranks = std::collections::HashSet::new();
+    for id in 0..CHESS960_POSITIONS {
+        back_ranks.insert(chess960_back_rank(id).unwrap());
+        let board = Board::new_chess960(id).unwrap();
+        assert!(board.validate().is_ok(), "position {}", id);
+        let fen = Board::from_fen(&board.to_fen()).unwrap();
+        assert_eq!(fen.castling, board.castling, "position {}", id);
+        assert_eq!(fen.perft(2), board.perft(2), "position {}", id);
+    }
+    assert_eq!(back_ranks.len(), CHESS960_POSITIONS);
+    assert!(Board::new_chess960(CHESS960_POSITIONS).is_none());
+}
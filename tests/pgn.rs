@@ -0,0 +1,168 @@
+use termichess::game::{parse_pgn, Board, GameInfo};
+
+fn play(board: &Board, moves: &[&str]) -> Board {
+    let mut board = board.clone();
+    for mv in moves {
+        board = board.perform(board.translate(mv).unwrap()).unwrap();
+    }
+    board
+}
+
+fn info() -> GameInfo {
+    GameInfo {
+        event: "Casual \"blitz\"".to_string(),
+        site: "TermiChess".to_string(),
+        date: "2024.01.31".to_string(),
+        round: "-".to_string(),
+        white: "Alice".to_string(),
+        black: "Bob".to_string(),
+    }
+}
+
+#[test]
+fn export() {
+    let mate = play(&Board::new(), &["f3", "e5", "g4", "Qh4"]);
+    assert_eq!(
+        mate.to_pgn(&info()).unwrap(),
+        "[Event \"Casual \\\"blitz\\\"\"]\n\
+         [Site \"TermiChess\"]\n\
+         [Date \"2024.01.31\"]\n\
+         [Round \"-\"]\n\
+         [White \"Alice\"]\n\
+         [Black \"Bob\"]\n\
+         [Result \"0-1\"]\n\
+         [Termination \"normal\"]\n\
+         \n\
+         1. f3 e5 2. g4 Qh4# {Checkmate} 0-1\n"
+    );
+
+    let ongoing = play(&Board::new(), &["e4"]);
+    assert!(ongoing.to_pgn(&info()).unwrap().ends_with("\n1. e4 *\n"));
+}
+
+#[test]
+fn export_from_a_position() {
+    let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12";
+    let board = play(&Board::from_fen(fen).unwrap(), &["Kd7", "e4"]);
+    let pgn = board.to_pgn(&info()).unwrap();
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n"));
+    assert!(pgn.ends_with("\n12... Kd7 13. e4 *\n"));
+
+    let chess960 = Board::new_chess960(518).unwrap().to_pgn(&info()).unwrap();
+    assert!(chess960.contains("[Variant \"Chess960\"]\n[SetUp \"1\"]\n"));
+
+    // A starting position that cannot be read is not exported from the standard one instead
+    let mut broken = board.clone();
+    broken.setup = Some("4k3/8/8 b - - 0 12".to_string());
+    assert!(broken.to_pgn(&info()).is_err());
+}
+
+#[test]
+fn export_long_games() {
+    let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+    let mut board = Board::new();
+    for _ in 0..4 {
+        board = play(&board, &shuffle);
+    }
+    let pgn = board.to_pgn(&info()).unwrap();
+    assert!(pgn.lines().all(|line| line.len() < 80), "{}", pgn);
+    assert!(
+        pgn.ends_with(" Ng8 {Draw by repetition} 1/2-1/2\n"),
+        "{}",
+        pgn
+    );
+    assert!(pgn.contains("\n[Result \"1/2-1/2\"]\n"));
+
+    // What is written can be read back move for move
+    let games = parse_pgn(&pgn).unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].tag("White"), Some("Alice"));
+    assert_eq!(games[0].result, "1/2-1/2");
+    let boards = games[0].replay().unwrap();
+    assert_eq!(boards.last().unwrap().moves, board.moves);
+}
+
+const ANNOTATED: &str = r#"[Event "Club \"Open\""]
+[Site "Somewhere"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+{An old line} 1. e4 e5 2. Nf3 Nc6 (2... d6 $2 {Philidor} 3. d4 (3. Bc4)) 3. Bb5!? ; the Spanish
+a6 4.Ba4 $1 1-0
+
+% escaped for another program
+[Event "Second"]
+
+1. d4 d5 *
+"#;
+
+#[test]
+fn import() {
+    let games = parse_pgn(ANNOTATED).unwrap();
+    assert_eq!(games.len(), 2);
+
+    let game = &games[0];
+    assert_eq!(game.tag("Event"), Some("Club \"Open\""));
+    assert_eq!(game.tag("Black"), Some("Bob"));
+    assert_eq!(game.tag("ECO"), None);
+    assert_eq!(game.result, "1-0");
+    assert_eq!(game.comments, vec!["An old line"]);
+
+    let sans = game
+        .moves
+        .iter()
+        .map(|mv| mv.san.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(sans, ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Ba4"]);
+    assert_eq!(game.moves[4].nags, vec![5]);
+    assert_eq!(game.moves[4].comments, vec!["the Spanish"]);
+    assert_eq!(game.moves[6].nags, vec![1]);
+
+    // Variations hang on the move they replace, and nest
+    let variation = &game.moves[3].variations[0];
+    assert_eq!(variation.len(), 2);
+    assert_eq!(variation[0].san, "d6");
+    assert_eq!(variation[0].nags, vec![2]);
+    assert_eq!(variation[0].comments, vec!["Philidor"]);
+    assert_eq!(variation[1].variations[0][0].san, "Bc4");
+
+    assert_eq!(game.replay().unwrap().len(), 8);
+    assert_eq!(games[1].tag("Event"), Some("Second"));
+    assert_eq!(games[1].result, "*");
+    assert_eq!(games[1].replay().unwrap().last().unwrap().moves.len(), 2);
+}
+
+#[test]
+fn import_from_a_position() {
+    let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n\n12... Kd7 13. e4";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games[0].result, "*");
+    let boards = games[0].replay().unwrap();
+    assert_eq!(boards[0].to_fen(), "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12");
+    assert_eq!(boards[2].to_fen(), "8/3k4/8/8/4P3/8/8/4K3 b - e3 0 13");
+}
+
+#[test]
+fn bad_movetext() {
+    for pgn in [
+        "[Event \"Unterminated\" 1. e4 *",
+        "[Event]\n1. e4 *",
+        "1. e4 {never closed *",
+        "1. e4 e5 (1... c5 *",
+        "1. e4 ) *",
+        "( 1. e4 ) *",
+        "1. e4 e5 [Event \"Late\"] *",
+        "$1 1. e4 *",
+        "1. e4?!? *",
+    ] {
+        assert!(parse_pgn(pgn).is_err(), "{}", pgn);
+    }
+
+    // Well formed, but not playable
+    let games = parse_pgn("1. e4 e5 2. Ke3 *").unwrap();
+    let err = games[0].replay().unwrap_err();
+    assert!(err.msg.contains("Illegal move Ke3 at move 2"), "{}", err);
+    let games = parse_pgn("[FEN \"8/8/8 w - - 0 1\"]\n1. e4 *").unwrap();
+    assert!(games[0].replay().is_err());
+}
@@ -0,0 +1,66 @@
+use termichess::game::{Board, CastleSide, Variant};
+
+fn san(fen: &str, notation: &str) -> String {
+    let board = Board::from_fen(fen).unwrap();
+    board.to_san(board.translate(notation).unwrap())
+}
+
+#[test]
+fn disambiguation() {
+    let board = Board::new();
+    assert_eq!(board.to_san(board.translate("g1f3").unwrap()), "Nf3");
+
+    // By file, by rank, and by both when neither is enough
+    let knights = "4k3/8/8/8/8/2N5/8/4K1N1 w - - 0 1";
+    assert_eq!(san(knights, "g1e2"), "Nge2");
+    assert_eq!(san(knights, "c3e2"), "Nce2");
+    let rooks = "4k3/8/8/8/8/R7/8/R3K3 w - - 0 1";
+    assert_eq!(san(rooks, "a1a2"), "R1a2");
+    assert_eq!(san(rooks, "a3a2"), "R3a2");
+    let queens = "4k3/8/8/8/8/Q1Q5/8/Q3K3 w - - 0 1";
+    assert_eq!(san(queens, "a3b2"), "Qa3b2");
+    assert_eq!(san(queens, "c3b2"), "Qcb2");
+
+    // A pinned piece cannot move, so it needs no telling apart
+    assert_eq!(san("4k3/8/8/8/8/8/8/r1N1KN2 w - - 0 1", "f1e3"), "Ne3");
+}
+
+#[test]
+fn captures_and_promotions() {
+    assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), "exd6");
+    assert_eq!(san("4k3/8/8/8/8/8/3p4/2N1K3 w - - 0 1", "c1d3"), "Nd3");
+    assert_eq!(san("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1", "Kxd2"), "Kxd2");
+
+    let promotion = "3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1";
+    assert_eq!(san(promotion, "e8=Q"), "e8=Q+");
+    assert_eq!(san(promotion, "e8=N"), "e8=N");
+    assert_eq!(san(promotion, "exd8=R"), "exd8=R+");
+}
+
+#[test]
+fn checks_and_mates() {
+    let board = ["f3", "e5", "g4"].iter().fold(Board::new(), |board, mv| {
+        board.perform(board.translate(mv).unwrap()).unwrap()
+    });
+    assert_eq!(board.to_san(board.translate("d8h4").unwrap()), "Qh4#");
+    assert_eq!(san("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8"), "Ra8+");
+    assert_eq!(san("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"), "Ra8#");
+}
+
+#[test]
+fn castling() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    assert_eq!(san(fen, "O-O"), "O-O");
+    assert_eq!(san(fen, "e1c1"), "O-O-O");
+    assert_eq!(san("4k3/8/8/8/8/8/8/R3K2R b KQ - 0 1", "Kd8"), "Kd8");
+    assert_eq!(san("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", "O-O-O"), "O-O-O+");
+
+    // In Chess960 the king takes its own rook, and is still written as castling
+    let mut board = Board::new_chess960(0).unwrap();
+    assert_eq!(board.variant, Variant::Chess960);
+    board = ["g3", "g6", "Ng2", "Ng7"].iter().fold(board, |board, mv| {
+        board.perform(board.translate(mv).unwrap()).unwrap()
+    });
+    let mv = board.castling_move(CastleSide::King).unwrap();
+    assert_eq!(board.to_san(mv), "O-O");
+}
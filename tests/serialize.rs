@@ -0,0 +1,275 @@
+use termichess::game::{
+    Board, BoardRecord, Move, Piece, PieceColor, PieceKind, PositionProblem, RecordError, Square,
+    Status, Termination, Variant, START_FEN,
+};
+
+fn play(board: &Board, moves: &[&str]) -> Board {
+    let mut board = board.clone();
+    for mv in moves {
+        board = board.perform(board.translate(mv).unwrap()).unwrap();
+    }
+    board
+}
+
+/// Checks that the board comes back the same through both the text and the JSON format
+fn round_trip(board: &Board) {
+    let text = Board::deserialize(board.serialize()).expect("text round trip");
+    let json = Board::from_json(&board.to_json()).expect("JSON round trip");
+    for restored in [text, json] {
+        assert_eq!(restored.serialize(), board.serialize());
+        assert_eq!(restored.to_fen(), board.to_fen());
+        assert_eq!(restored.turn, board.turn);
+        assert_eq!(restored.moves, board.moves);
+        assert_eq!(restored.history, board.history);
+        assert_eq!(restored.status, board.status);
+        assert_eq!(restored.termination, board.termination);
+        assert_eq!(restored.draw_offer, board.draw_offer);
+        assert_eq!(restored.variant, board.variant);
+    }
+}
+
+/// Writes the board in the `/`-separated format of releases before the versioned one
+fn legacy(board: &Board) -> String {
+    let mut squares = String::new();
+    for file in 0..8 {
+        for rank in 0..8 {
+            squares.push_str(&board.get(Square::new(file, rank).unwrap()).serialize());
+        }
+    }
+    let moves = board.moves.iter().map(Move::serialize).collect::<String>();
+    format!(
+        "{}/{}/{}/{}/f",
+        squares,
+        moves,
+        board.turn,
+        board.status.serialize()
+    )
+}
+
+#[test]
+fn layout() {
+    let board = play(&Board::new(), &["e4"]);
+    assert_eq!(
+        board.serialize(),
+        format!(
+            "v4;standard;{};e2e4;rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1;P;-;f",
+            START_FEN
+        )
+    );
+    round_trip(&Board::new());
+    round_trip(&board);
+}
+
+#[test]
+fn draw_offers() {
+    let mut board = play(&Board::new(), &["e4"]);
+    board.draw();
+    assert!(board.serialize().contains("b KQkq e3 0 1"));
+    round_trip(&board);
+
+    // Accepted, the offer stays on the finished board
+    board.draw();
+    assert_eq!(board.termination, Some(Termination::Agreement));
+    round_trip(&board);
+
+    let mut board = play(&Board::new(), &["e4"]);
+    board.draw();
+    board.decline_draw();
+    round_trip(&board);
+}
+
+#[test]
+fn finished_games() {
+    let mate = play(&Board::new(), &["f3", "e5", "g4", "Qh4"]);
+    assert_eq!(mate.status, Status::Black);
+    round_trip(&mate);
+
+    let mut resigned = play(&Board::new(), &["d4", "d5"]);
+    resigned.resign();
+    round_trip(&resigned);
+}
+
+#[test]
+fn chess960() {
+    let board = Board::new_chess960(0).unwrap();
+    round_trip(&board);
+    round_trip(&play(&board, &["g3", "g6", "Ng2"]));
+}
+
+#[test]
+fn legacy_boards() {
+    // Castling rights and en passant are worked out from the pieces and the moves
+    let board = play(&Board::new(), &["e4", "a6", "e5", "d5"]);
+    let restored = Board::deserialize(legacy(&board)).unwrap();
+    assert_eq!(restored.to_fen(), board.to_fen());
+    assert_eq!(restored.moves, board.moves);
+    assert!(restored.translate("exd6").is_ok());
+
+    let board = play(&Board::new(), &["e4", "a6", "e5", "a5", "Ke2", "Ra7"]);
+    let restored = Board::deserialize(legacy(&board)).unwrap();
+    assert_eq!(restored.to_fen().split(' ').nth(2), Some("k"));
+
+    // Only the five fields of the old format are read
+    assert!(Board::deserialize(format!("{}/0", legacy(&board))).is_err());
+    assert!(Board::deserialize("v2;nonsense".to_string()).is_err());
+}
+
+#[test]
+fn serde_types() {
+    fn json<T>(value: T)
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let text = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&text).unwrap(), value, "{}", text);
+    }
+
+    let board = play(&Board::new(), &["e4", "c5", "Nf3"]);
+    json(board.translate("d6").unwrap());
+    json(Square::from_notation("h8").unwrap());
+    json(board.get(Square::from_notation("f3").unwrap()));
+    json(Piece::empty());
+    json(PieceKind::Knight);
+    json(PieceColor::Black);
+    json(Status::Draw);
+    json(Termination::InsufficientMaterial);
+    json(Variant::Chess960);
+    json(board.castling);
+    json(board.to_record());
+
+    // Boards go through their record, and come back with the caches rebuilt
+    let text = serde_json::to_string(&board).unwrap();
+    let record: BoardRecord = serde_json::from_str(&text).unwrap();
+    assert_eq!(record, board.to_record());
+    assert_eq!(record.start, START_FEN);
+    assert_eq!(record.moves, ["e2e4", "c7c5", "g1f3"]);
+    assert_eq!(record.status, Status::Playing);
+    assert!(text.contains("\"status\":\"Playing\""), "{}", text);
+    let restored: Board = serde_json::from_str(&text).unwrap();
+    assert_eq!(restored.history, board.history);
+    assert_eq!(restored.legal_moves(), board.legal_moves());
+    round_trip(&board);
+}
+
+#[test]
+fn terminations() {
+    let mate = play(&Board::new(), &["f3", "e5", "g4", "Qh4"]);
+    let stalemate = play(
+        &Board::from_fen("k7/8/1Q6/8/8/8/8/7K w - - 0 1").unwrap(),
+        &["Qc7"],
+    );
+    let mut resignation = play(&Board::new(), &["e4"]);
+    resignation.resign();
+    let mut agreement = play(&Board::new(), &["e4"]);
+    agreement.draw();
+    agreement.draw();
+    let mut abandonment = play(&Board::new(), &["e4"]);
+    abandonment.abandon(PieceColor::White);
+    let mut timeout = play(&Board::new(), &["e4"]);
+    timeout.set_time_control("1+0".parse().unwrap());
+    timeout.flag(PieceColor::Black);
+
+    for (board, status, termination) in [
+        (mate, Status::Black, Termination::Checkmate),
+        (stalemate, Status::Draw, Termination::Stalemate),
+        (resignation, Status::White, Termination::Resignation),
+        (agreement, Status::Draw, Termination::Agreement),
+        (abandonment, Status::Black, Termination::Abandonment),
+        (timeout, Status::White, Termination::Timeout),
+    ] {
+        assert_eq!(board.status, status, "{:?}", termination);
+        assert_eq!(board.termination, Some(termination));
+        let text = board.serialize();
+        assert!(
+            text.contains(&format!(
+                ";{};{};",
+                status.serialize(),
+                termination.serialize()
+            )),
+            "{}",
+            text
+        );
+        round_trip(&board);
+    }
+
+    // Every reason has a name of its own in both formats
+    let all = [
+        Termination::Checkmate,
+        Termination::Resignation,
+        Termination::Timeout,
+        Termination::Abandonment,
+        Termination::Stalemate,
+        Termination::Agreement,
+        Termination::Repetition,
+        Termination::FiftyMoves,
+        Termination::InsufficientMaterial,
+    ];
+    for termination in all {
+        assert_eq!(
+            Termination::deserialize(termination.serialize()),
+            Some(termination)
+        );
+        let json = serde_json::to_string(&termination).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Termination>(&json).unwrap(),
+            termination
+        );
+    }
+    assert_eq!(Termination::deserialize("flagged".to_string()), None);
+    let flagged = format!("v4;standard;{};;{};D;flagged;f", START_FEN, START_FEN);
+    assert!(Board::deserialize(flagged).is_err());
+}
+
+#[test]
+fn bad_boards_are_rejected() {
+    // Malformed input is turned down rather than crashing the loader
+    assert!(Board::deserialize("Kwx-------/ /1/P/f".to_string()).is_err());
+    assert!(Board::deserialize("v4;standard;nonsense".to_string()).is_err());
+
+    let lone_king = format!("Kw0{}//1/P/f", "-".repeat(63));
+    let err = Board::deserialize(lone_king).unwrap_err();
+    assert!(err.to_string().contains("Black has no king"), "{}", err);
+    assert!(matches!(err, RecordError::Position(problems)
+        if problems.contains(&PositionProblem::MissingKing(PieceColor::Black))));
+
+    let json = Board::new()
+        .to_json()
+        .replace("\"moves\":[]", "\"moves\":[\"e2e5\"]");
+    let err = Board::from_json(&json).unwrap_err();
+    assert_eq!(err, RecordError::IllegalMove("e2e5".to_string()));
+    assert_eq!(err.to_string(), "Illegal move e2e5");
+
+    // Each part of the text format is checked on its own
+    let board = |fields: &str| Board::deserialize(format!("v{}", fields)).unwrap_err();
+    let start = format!("{};;{}", START_FEN, START_FEN);
+    assert_eq!(
+        board(&format!("3;standard;{};P;-;f", start)),
+        RecordError::UnsupportedVersion(3)
+    );
+    assert_eq!(
+        board(&format!("4;crazyhouse;{};P;-;f", start)),
+        RecordError::UnknownVariant("crazyhouse".to_string())
+    );
+    assert_eq!(
+        board(&format!("4;standard;{};X;-;f", start)),
+        RecordError::InvalidStatus("X".to_string())
+    );
+    assert_eq!(
+        board(&format!("4;standard;{};P;-;maybe", start)),
+        RecordError::InvalidDrawOffer("maybe".to_string())
+    );
+    assert!(matches!(
+        board(&format!(
+            "4;standard;8/8/8/8/8/8/8/8 w - - 0 1;;{};P;-;f",
+            START_FEN
+        )),
+        RecordError::Fen(_)
+    ));
+    assert_eq!(
+        board(&format!(
+            "4;standard;{};e2e4;{};P;-;f",
+            START_FEN, START_FEN
+        )),
+        RecordError::PositionMismatch(START_FEN.to_string())
+    );
+}
@@ -0,0 +1,62 @@
+use termichess::game::{Board, PieceColor};
+use termichess::local::Takebacks;
+use termichess::util::{parse_raw, Command};
+
+fn play(board: &mut Board, takebacks: &mut Takebacks, moves: &[&str]) {
+    for mv in moves {
+        let next = board.perform(board.translate(mv).unwrap()).unwrap();
+        takebacks.record(std::mem::replace(board, next));
+    }
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut board = Board::new();
+    let mut takebacks = Takebacks::new();
+    assert!(!takebacks.undo(&mut board, 1));
+
+    play(&mut board, &mut takebacks, &["e4", "e5", "Nf3"]);
+    let after = board.to_fen();
+    assert_eq!(takebacks.last_mover(), Some(PieceColor::White));
+
+    assert!(takebacks.undo(&mut board, 2));
+    assert_eq!(board.moves.len(), 1);
+    assert_eq!(board.which_color(), PieceColor::Black);
+    assert!(!takebacks.undo(&mut board, 2));
+
+    assert!(takebacks.redo(&mut board, 2));
+    assert_eq!(board.to_fen(), after);
+    assert!(!takebacks.can_redo(1));
+
+    // A new move after a takeback cannot be followed by the old ones
+    assert!(takebacks.undo(&mut board, 1));
+    play(&mut board, &mut takebacks, &["Nc3"]);
+    assert!(!takebacks.redo(&mut board, 1));
+    assert_eq!(board.moves.len(), 3);
+}
+
+#[test]
+fn clocks_keep_running() {
+    let mut board = Board::new();
+    board.set_time_control("5+3".parse().unwrap());
+    board.sync_clock();
+    let mut takebacks = Takebacks::new();
+
+    let next = board.perform(board.translate("e4").unwrap()).unwrap();
+    takebacks.record(std::mem::replace(&mut board, next));
+    board.sync_clock();
+    assert_eq!(board.clock.as_ref().unwrap().running(), Some(false));
+
+    // The increment earned by the move is kept, and White's clock runs again
+    assert!(takebacks.undo(&mut board, 1));
+    let clock = board.clock.as_ref().unwrap();
+    assert_eq!(clock.running(), Some(true));
+    assert!(clock.left(true, std::time::Instant::now()) > std::time::Duration::from_secs(300));
+}
+
+#[test]
+fn commands() {
+    assert_eq!(parse_raw("undo".to_string()), Command::Undo);
+    assert_eq!(parse_raw(" takeback ".to_string()), Command::Undo);
+    assert_eq!(parse_raw("redo".to_string()), Command::Redo);
+}
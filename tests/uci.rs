@@ -0,0 +1,59 @@
+use std::time::Duration;
+use termichess::game::PieceColor;
+use termichess::uci::{GoOptions, UciCommand};
+
+#[test]
+fn parses_position() {
+    assert_eq!(
+        UciCommand::parse("position startpos moves e2e4 e7e5"),
+        UciCommand::Position(None, vec!["e2e4".to_string(), "e7e5".to_string()])
+    );
+    assert_eq!(
+        UciCommand::parse("position fen 8/8/8/4k3/8/8/8/R3K3 w Q - 0 1"),
+        UciCommand::Position(Some("8/8/8/4k3/8/8/8/R3K3 w Q - 0 1".to_string()), vec![])
+    );
+}
+
+#[test]
+fn parses_options() {
+    assert_eq!(
+        UciCommand::parse("setoption name UCI_Chess960 value true"),
+        UciCommand::SetOption("UCI_Chess960".to_string(), "true".to_string())
+    );
+    assert_eq!(
+        UciCommand::parse("setoption name Clear Hash"),
+        UciCommand::SetOption("Clear Hash".to_string(), String::new())
+    );
+}
+
+#[test]
+fn parses_go() {
+    let UciCommand::Go(options) =
+        UciCommand::parse("go ponder wtime 60000 btime 30000 winc 1000 binc 0")
+    else {
+        panic!("not a go command");
+    };
+    assert_eq!(options.wtime, Some(60000));
+    assert_eq!(options.btime, Some(30000));
+    assert!(!options.infinite);
+
+    // A share of the clock plus most of the increment
+    let limits = options.limits(PieceColor::White);
+    assert_eq!(limits.movetime, Some(Duration::from_millis(2750)));
+    assert_eq!(
+        GoOptions {
+            movetime: Some(500),
+            ..Default::default()
+        }
+        .limits(PieceColor::Black)
+        .movetime,
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(
+        UciCommand::parse("go infinite"),
+        UciCommand::Go(GoOptions {
+            infinite: true,
+            ..Default::default()
+        })
+    );
+}
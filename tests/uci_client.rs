@@ -0,0 +1,130 @@
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use termichess::engine::SearchLimits;
+use termichess::game::Board;
+use termichess::uci::UciEngine;
+
+// Answers the opening moves it knows and anything else with a move that is never legal
+const FAKE_ENGINE: &str = r#"#!/bin/sh
+while read -r line; do
+    case "$line" in
+        uci)
+            echo "id name Fake Engine"
+            echo "id author Nobody"
+            echo "uciok"
+            ;;
+        isready) echo "readyok" ;;
+        "position startpos") reply=e2e4 ;;
+        "position startpos moves e2e4") reply=e7e5 ;;
+        "position fen "*) reply=a1a8 ;;
+        position*) reply=e1e8 ;;
+        go*)
+            echo "info depth 1 score cp 20 pv $reply"
+            echo "bestmove $reply"
+            ;;
+        quit) exit 0 ;;
+    esac
+done
+"#;
+
+// Only answers `go` when told to stop, and logs the options it is given
+const SLOW_ENGINE: &str = r#"#!/bin/sh
+while read -r line; do
+    case "$line" in
+        uci)
+            echo "id name Slow Engine"
+            echo "option name UCI_Chess960 type check default false"
+            echo "uciok"
+            ;;
+        isready) echo "readyok" ;;
+        setoption*) echo "$line" >> "$0.log" ;;
+        stop) echo "bestmove e2e4" ;;
+        quit) exit 0 ;;
+    esac
+done
+"#;
+
+fn write_script(name: &str, script: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("termichess-{}-{}", name, std::process::id()));
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+#[test]
+fn plays_the_engine_moves() {
+    let path = write_script("fake-engine", FAKE_ENGINE);
+    let mut engine = UciEngine::spawn(path.to_str().unwrap()).unwrap();
+    assert_eq!(engine.name, "Fake Engine");
+    engine.new_game().unwrap();
+
+    // The engine is told the whole game, so its reply depends on the moves so far
+    let limits = SearchLimits::depth(1);
+    let mut board = Board::new();
+    let mv = engine.best_move(&board, limits).unwrap();
+    assert_eq!(mv.to_string(), "e2e4");
+    board = board.perform(mv).unwrap();
+    assert_eq!(
+        engine.best_move(&board, limits).unwrap().to_string(),
+        "e7e5"
+    );
+
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    assert_eq!(
+        engine.best_move(&board, limits).unwrap().to_string(),
+        "a1a8"
+    );
+
+    // Illegal replies are refused rather than played
+    let board = Board::new()
+        .perform(Board::new().translate("d4").unwrap())
+        .unwrap();
+    assert!(engine.best_move(&board, limits).is_err());
+    // Nor can it play Chess960 without saying so
+    assert!(engine
+        .best_move(&Board::new_chess960(0).unwrap(), limits)
+        .is_err());
+
+    drop(engine);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn fails_when_the_engine_exits() {
+    let path = write_script("broken-engine", "#!/bin/sh\nexit 1\n");
+    assert!(UciEngine::spawn(path.to_str().unwrap()).is_err());
+    std::fs::remove_file(path).unwrap();
+
+    assert!(UciEngine::spawn("/nonexistent/engine").is_err());
+}
+
+#[test]
+fn stops_a_silent_engine() {
+    let path = write_script("slow-engine", SLOW_ENGINE);
+    let log = path.with_extension("log");
+    let mut engine = UciEngine::spawn(path.to_str().unwrap()).unwrap();
+
+    let limits = SearchLimits::movetime(std::time::Duration::from_millis(100));
+    let err = engine.best_move(&Board::new(), limits).unwrap_err();
+    assert!(err.msg.contains("did not answer"), "{}", err);
+
+    // The answer to `stop` is not mistaken for the move of the next search
+    let err = engine
+        .best_move(&Board::new_chess960(0).unwrap(), limits)
+        .unwrap_err();
+    assert!(err.msg.contains("did not answer"), "{}", err);
+    assert!(engine.best_move(&Board::new(), limits).is_err());
+
+    drop(engine);
+    // Chess960 is turned off again for the standard game that follows
+    assert_eq!(
+        std::fs::read_to_string(&log).unwrap(),
+        "setoption name UCI_Chess960 value false\n\
+         setoption name UCI_Chess960 value true\n\
+         setoption name UCI_Chess960 value false\n"
+    );
+    std::fs::remove_file(path).unwrap();
+    std::fs::remove_file(log).unwrap();
+}
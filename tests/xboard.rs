@@ -0,0 +1,159 @@
+use std::time::Duration;
+use termichess::game::Board;
+use termichess::xboard::{run_xboard_with, XboardCommand};
+
+#[test]
+fn parses_moves_and_positions() {
+    assert_eq!(
+        XboardCommand::parse("usermove e7e8q"),
+        XboardCommand::UserMove("e7e8q".to_string())
+    );
+    assert_eq!(
+        XboardCommand::parse("setboard 8/8/8/4k3/8/8/8/R3K3 w Q - 0 1"),
+        XboardCommand::SetBoard("8/8/8/4k3/8/8/8/R3K3 w Q - 0 1".to_string())
+    );
+    assert_eq!(
+        XboardCommand::parse("e2e4"),
+        XboardCommand::Unknown("e2e4".to_string())
+    );
+}
+
+#[test]
+fn parses_time_controls() {
+    assert_eq!(
+        XboardCommand::parse("level 40 5 0"),
+        XboardCommand::Level(40, Duration::from_secs(300), Duration::ZERO)
+    );
+    assert_eq!(
+        XboardCommand::parse("level 0 2:30 1.5"),
+        XboardCommand::Level(0, Duration::from_secs(150), Duration::from_millis(1500))
+    );
+    assert_eq!(XboardCommand::parse("time 6000"), XboardCommand::Time(6000));
+    assert_eq!(
+        XboardCommand::parse("st 0.5"),
+        XboardCommand::St(Duration::from_millis(500))
+    );
+    assert_eq!(XboardCommand::parse("sd 4"), XboardCommand::Sd(4));
+}
+
+#[test]
+fn parses_control_commands() {
+    assert_eq!(
+        XboardCommand::parse("protover 2"),
+        XboardCommand::Protover(2)
+    );
+    assert_eq!(
+        XboardCommand::parse("ping 17"),
+        XboardCommand::Ping("17".to_string())
+    );
+    assert_eq!(
+        XboardCommand::parse("result 1-0 {White mates}"),
+        XboardCommand::Result("1-0 {White mates}".to_string())
+    );
+    assert_eq!(
+        XboardCommand::parse("accepted usermove"),
+        XboardCommand::Ignored
+    );
+}
+
+/// Sends the commands through the xboard loop, returning every line the engine answered
+fn session(commands: &[&str]) -> Vec<String> {
+    let mut output = Vec::new();
+    run_xboard_with(commands.join("\n").as_bytes(), &mut output);
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn plays_moves() {
+    let lines = session(&["xboard", "protover 2", "new", "sd 1", "usermove e2e4"]);
+    assert!(lines[0].starts_with("feature "), "{:?}", lines);
+    assert!(lines[0].ends_with(" done=1"), "{:?}", lines);
+    // The engine answers with Black after the user's move
+    let reply = lines[1].strip_prefix("move ").unwrap();
+    let board = Board::new();
+    let board = board.perform(board.translate("e4").unwrap()).unwrap();
+    assert!(board.translate(reply).is_ok(), "{}", reply);
+    assert_eq!(lines.len(), 2);
+
+    // In force mode moves are only recorded, until `go` hands the side to move to the engine
+    let lines = session(&["new", "sd 1", "force", "usermove e2e4", "usermove e7e5"]);
+    assert!(lines.is_empty(), "{:?}", lines);
+    let lines = session(&["new", "sd 1", "force", "e2e4", "e7e5", "go"]);
+    assert_eq!(lines.len(), 1, "{:?}", lines);
+    assert!(lines[0].starts_with("move "), "{:?}", lines);
+
+    let lines = session(&["new", "force", "usermove e2e5", "zzz"]);
+    assert!(lines[0].starts_with("Illegal move ("), "{:?}", lines);
+    assert!(lines[0].ends_with("): e2e5"), "{:?}", lines);
+    assert_eq!(lines[1], "Error (unknown command): zzz");
+}
+
+#[test]
+fn sets_up_positions() {
+    // A mate in one is found and the result announced
+    let lines = session(&[
+        "new",
+        "force",
+        "setboard 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+        "sd 2",
+        "go",
+    ]);
+    assert_eq!(lines, ["move a1a8", "1-0 {Checkmate}"]);
+
+    let lines = session(&["new", "setboard 8/8/8/8/8/8/8/8 w - - 0 1", "ping 1"]);
+    assert!(
+        lines[0].starts_with("tellusererror Illegal position: "),
+        "{:?}",
+        lines
+    );
+    assert_eq!(lines[1], "pong 1");
+}
+
+#[test]
+fn switches_variants() {
+    // Back in standard chess the king castles by moving two squares again
+    let opening = ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6"];
+    let mut commands = vec!["new", "force", "variant fischerandom"];
+    commands.extend(opening);
+    commands.extend(["variant normal", "usermove e1g1", "ping 1"]);
+    assert_eq!(session(&commands), ["pong 1"]);
+
+    // A Chess960 position cannot be played as standard chess
+    let lines = session(&[
+        "new",
+        "force",
+        "variant fischerandom",
+        "setboard nrkbbqrn/pppppppp/8/8/8/8/PPPPPPPP/NRKBBQRN w GBgb - 0 1",
+        "variant normal",
+        "variant crazyhouse",
+    ]);
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+    assert!(
+        lines[0].starts_with("tellusererror Illegal position: "),
+        "{:?}",
+        lines
+    );
+    assert_eq!(lines[1], "Error (unsupported variant): crazyhouse");
+}
+
+#[test]
+fn answers_draw_offers() {
+    // The engine plays Black, and only offers a draw back when it is worse off
+    let losing = session(&["new", "setboard 4k3/8/8/8/8/8/QQ6/4K3 w - - 0 1", "draw"]);
+    assert_eq!(losing, ["offer draw"]);
+    let winning = session(&["new", "setboard 4k3/qq6/8/8/8/8/8/4K3 w - - 0 1", "draw"]);
+    assert!(winning.is_empty(), "{:?}", winning);
+
+    // Without a side of its own the engine has nothing to judge
+    let forced = session(&[
+        "new",
+        "force",
+        "setboard 4k3/8/8/8/8/8/QQ6/4K3 w - - 0 1",
+        "draw",
+    ]);
+    assert!(forced.is_empty(), "{:?}", forced);
+}